@@ -0,0 +1,87 @@
+use super::{options::ModelEntry, MainOptions};
+use quote::quote;
+use syn::{parse::Parse, punctuated::Punctuated, Result, Token};
+
+// export_models!(domain_models: user::User, order_list = order::Order)
+pub struct ExportModels {
+    pub name: syn::Ident,
+    pub models: Punctuated<ModelEntry, Token![,]>,
+}
+
+impl Parse for ExportModels {
+    fn parse(input: syn::parse::ParseStream) -> Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![:]>()?;
+        let models = Punctuated::parse_terminated(input)?;
+        Ok(Self { name, models })
+    }
+}
+
+// 生成导出模型列表的宏，模型路径相对于定义该宏的crate，展开时通过$crate引用
+pub fn export_models(export: &ExportModels) -> Result<proc_macro2::TokenStream> {
+    let name = &export.name;
+    let mut models = Vec::new();
+    for model in export.models.iter() {
+        let path = &model.ty.path;
+        if model.ty.qself.is_some() || path.leading_colon.is_some() {
+            return Err(syn::Error::new_spanned(
+                &model.ty,
+                "exported model must be a path relative to the crate root, e.g. user::User",
+            ));
+        }
+        let segments = path
+            .segments
+            .iter()
+            .skip_while(|segment| segment.ident == "crate");
+        let ty = quote!($crate #(::#segments)*);
+        models.push(match &model.field {
+            Some(field) => quote!(#field = #ty),
+            None => ty,
+        });
+    }
+    Ok(quote! {
+        #[doc(hidden)]
+        #[macro_export]
+        macro_rules! #name {
+            ($($options:tt)*) => {
+                axum_mongodb::__private::include_models! {
+                    [#(#models),*] $($options)*
+                }
+            };
+        }
+    })
+}
+
+// include_models!{ [导出的模型] main上的属性 }，将导出的模型追加到models中后继续生成Servers
+pub struct IncludeModels {
+    pub options: MainOptions,
+}
+
+impl Parse for IncludeModels {
+    fn parse(input: syn::parse::ParseStream) -> Result<Self> {
+        let content;
+        syn::bracketed!(content in input);
+        let models = content.parse_terminated(ModelEntry::parse, Token![,])?;
+        let mut options = MainOptions::default();
+        let parser = syn::meta::parser(|meta| options.parse_meta(meta));
+        syn::parse::Parser::parse2(parser, input.parse()?)?;
+        options.models.extend(models);
+        Ok(Self { options })
+    }
+}
+
+// 生成Servers，include中的宏依次展开，全部展开后交给inject_meta
+pub fn servers(options: &MainOptions) -> proc_macro2::TokenStream {
+    let mut options = options.clone();
+    if options.includes.is_empty() {
+        quote! {
+            #[axum_mongodb::inject_meta(#options)]
+            pub struct Servers {}
+        }
+    } else {
+        let include = options.includes.remove(0);
+        quote! {
+            #include! { #options }
+        }
+    }
+}
@@ -0,0 +1,305 @@
+use quote::{format_ident, quote};
+use syn::{ext::IdentExt, LitStr, Result};
+
+// 模型中的一个字段，name为文档中的字段名，已经应用了serde的rename以及rename_all
+pub struct FieldMeta {
+    pub ident: syn::Ident,
+    pub name: String,
+    pub vis: syn::Visibility,
+    pub ty: syn::Type,
+    pub serializer: Serializer,
+    // #[column(immutable)]，创建后不能修改，不生成set_方法，也不会出现在{Model}Patch中
+    pub immutable: bool,
+}
+
+impl FieldMeta {
+    pub fn is_mutable(&self) -> bool {
+        self.name != "_id" && !self.immutable
+    }
+}
+
+// 字段的序列化方式，查询条件中的值需要与文档中的值以相同的方式序列化
+#[derive(Clone)]
+pub enum Serializer {
+    Default,
+    // #[serde(serialize_with = "path")]
+    SerializeWith(syn::Path),
+    // #[serde(with = "module")]
+    With(syn::Path),
+}
+
+impl Serializer {
+    // 将字段的值转换为BSON的函数，以及对应的where子句
+    pub fn to_bson(&self, ty: &syn::Type) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+        let bound = self.bound(ty).map(|bound| quote!(where #bound));
+        let serialize = match self {
+            Self::Default => quote!(axum_mongodb::__private::mongodb::bson::to_bson::<#ty>),
+            Self::SerializeWith(path) => quote! {
+                |value| #path(value, axum_mongodb::__private::mongodb::bson::Serializer::new())
+            },
+            Self::With(path) => quote! {
+                |value| #path::serialize(value, axum_mongodb::__private::mongodb::bson::Serializer::new())
+            },
+        };
+        (serialize, quote!(#bound))
+    }
+
+    // 使用serde默认序列化时需要的约束，字段类型可能包含泛型参数
+    pub fn bound(&self, ty: &syn::Type) -> Option<proc_macro2::TokenStream> {
+        match self {
+            Self::Default => Some(quote!(#ty: axum_mongodb::__private::serde::Serialize)),
+            Self::SerializeWith(_) | Self::With(_) => None,
+        }
+    }
+}
+
+// 结构体上的#[serde(rename_all = "...")]
+#[derive(Debug, Clone, Copy)]
+pub enum RenameAll {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameAll {
+    fn parse(value: &LitStr) -> Result<Self> {
+        match value.value().as_str() {
+            "lowercase" => Ok(Self::Lower),
+            "UPPERCASE" => Ok(Self::Upper),
+            "PascalCase" => Ok(Self::Pascal),
+            "camelCase" => Ok(Self::Camel),
+            "snake_case" => Ok(Self::Snake),
+            "SCREAMING_SNAKE_CASE" => Ok(Self::ScreamingSnake),
+            "kebab-case" => Ok(Self::Kebab),
+            "SCREAMING-KEBAB-CASE" => Ok(Self::ScreamingKebab),
+            _ => Err(syn::Error::new_spanned(
+                value,
+                "unsupported rename_all rule",
+            )),
+        }
+    }
+
+    // 与serde一致，字段名视为snake_case
+    fn apply(&self, field: &str) -> String {
+        match self {
+            Self::Lower | Self::Snake => field.to_string(),
+            Self::Upper | Self::ScreamingSnake => field.to_ascii_uppercase(),
+            Self::Pascal => pascal_case(field),
+            Self::Camel => {
+                let pascal = pascal_case(field);
+                let mut chars = pascal.chars();
+                match chars.next() {
+                    Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+                    None => pascal,
+                }
+            }
+            Self::Kebab => field.replace('_', "-"),
+            Self::ScreamingKebab => field.replace('_', "-").to_ascii_uppercase(),
+        }
+    }
+}
+
+fn pascal_case(field: &str) -> String {
+    field
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+// 解析#[serde(...)]中的rename_all，其他选项交给serde处理
+pub fn parse_rename_all(attrs: &[syn::Attribute]) -> Result<Option<RenameAll>> {
+    let mut rename_all = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename_all") {
+                rename_all = parse_rename(&meta)?
+                    .map(|value| RenameAll::parse(&value))
+                    .transpose()?;
+            } else {
+                skip_meta(&meta)?;
+            }
+            Ok(())
+        })?;
+    }
+    Ok(rename_all)
+}
+
+// 获取字段在文档中的名字，使用skip或flatten的字段返回None
+pub fn field_meta(field: &syn::Field, rename_all: Option<RenameAll>) -> Result<Option<FieldMeta>> {
+    let ident = field.ident.clone().expect("named field");
+    let mut name = None;
+    let mut skip = false;
+    let mut serializer = Serializer::Default;
+    let mut immutable = false;
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("column"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("immutable") {
+                immutable = true;
+                return Ok(());
+            }
+            Err(meta.error("unsupported column field attribute"))
+        })?;
+    }
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("serde"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                name = parse_rename(&meta)?.map(|value| value.value());
+            } else if meta.path.is_ident("skip")
+                || meta.path.is_ident("skip_serializing")
+                || meta.path.is_ident("flatten")
+            {
+                skip = true;
+            } else if meta.path.is_ident("serialize_with") {
+                serializer = Serializer::SerializeWith(meta.value()?.parse::<LitStr>()?.parse()?);
+            } else if meta.path.is_ident("with") {
+                serializer = Serializer::With(meta.value()?.parse::<LitStr>()?.parse()?);
+            } else {
+                skip_meta(&meta)?;
+            }
+            Ok(())
+        })?;
+    }
+    if skip {
+        return Ok(None);
+    }
+    let name = name.unwrap_or_else(|| {
+        let field = ident.unraw().to_string();
+        match rename_all {
+            Some(rename_all) => rename_all.apply(&field),
+            None => field,
+        }
+    });
+    Ok(Some(FieldMeta {
+        ident,
+        name,
+        vis: field.vis.clone(),
+        ty: field.ty.clone(),
+        serializer,
+        immutable,
+    }))
+}
+
+// rename = "..."或rename(serialize = "...", deserialize = "...")，以serialize为准
+fn parse_rename(meta: &syn::meta::ParseNestedMeta) -> Result<Option<LitStr>> {
+    if meta.input.peek(syn::Token![=]) {
+        return Ok(Some(meta.value()?.parse()?));
+    }
+    let mut serialize = None;
+    meta.parse_nested_meta(|meta| {
+        if meta.path.is_ident("serialize") {
+            serialize = Some(meta.value()?.parse()?);
+        } else {
+            skip_meta(&meta)?;
+        }
+        Ok(())
+    })?;
+    Ok(serialize)
+}
+
+// 跳过不关心的serde选项，例如default、with = "..."、skip_serializing_if = "..."
+fn skip_meta(meta: &syn::meta::ParseNestedMeta) -> Result<()> {
+    if meta.input.peek(syn::Token![=]) {
+        meta.value()?.parse::<syn::Expr>()?;
+    } else if meta.input.peek(syn::token::Paren) {
+        meta.parse_nested_meta(|meta| skip_meta(&meta))?;
+    }
+    Ok(())
+}
+
+// 为模型生成{Model}Fields中的字段名常量以及{Model}Field枚举，常量不放在模型上，避免与模型自己的关联常量冲突
+pub fn fields(st: &syn::DeriveInput, fields: &[FieldMeta]) -> proc_macro2::TokenStream {
+    let struct_name = &st.ident;
+    let vis = &st.vis;
+    let enum_name = format_ident!("{}Field", struct_name);
+    let consts_name = format_ident!("{}Fields", struct_name);
+    let (impl_generics, ty_generics, where_clause) = st.generics.split_for_impl();
+    let consts = fields.iter().map(|field| {
+        let const_name = format_ident!("{}", field.ident.unraw().to_string().to_ascii_uppercase());
+        let name = &field.name;
+        let doc = format!("字段`{}`在文档中的名字`{}`", field.ident.unraw(), name);
+        quote! {
+            #[doc = #doc]
+            pub const #const_name: &'static str = #name;
+        }
+    });
+    let variants = fields
+        .iter()
+        .map(|field| format_ident!("{}", pascal_case(&field.ident.unraw().to_string())))
+        .collect::<Vec<_>>();
+    let names = fields.iter().map(|field| &field.name).collect::<Vec<_>>();
+    let idents = fields.iter().map(|field| field.ident.unraw().to_string());
+    let doc = format!("`{}`的字段，`as_str`返回文档中的字段名", struct_name);
+    let consts_doc = format!("`{}`中字段在文档中的名字", struct_name);
+    quote! {
+        #[doc = #consts_doc]
+        #[allow(dead_code)]
+        #vis struct #consts_name;
+
+        impl #consts_name {
+            #(#consts)*
+        }
+
+        #[doc = #doc]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #vis enum #enum_name {
+            #(#variants),*
+        }
+
+        impl #enum_name {
+            pub const fn as_str(&self) -> &'static str {
+                match *self {
+                    #(Self::#variants => #names),*
+                }
+            }
+        }
+
+        impl axum_mongodb::Field for #enum_name {
+            fn name(&self) -> &'static str {
+                self.as_str()
+            }
+        }
+
+        impl AsRef<str> for #enum_name {
+            fn as_ref(&self) -> &str {
+                self.as_str()
+            }
+        }
+
+        impl std::fmt::Display for #enum_name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl From<#enum_name> for String {
+            fn from(field: #enum_name) -> Self {
+                field.as_str().to_string()
+            }
+        }
+
+        // 索引键中的嵌套路径通过Rust字段名查找文档中的字段名
+        impl #impl_generics axum_mongodb::NestedFields for #struct_name #ty_generics #where_clause {
+            type Document = Self;
+            const FIELDS: &'static [(&'static str, &'static str)] = &[#((#idents, #names)),*];
+        }
+    }
+}
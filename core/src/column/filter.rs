@@ -0,0 +1,50 @@
+use super::fields::FieldMeta;
+use quote::{format_ident, quote};
+
+// 为模型生成{Model}Filter，Model::filter().field()得到字段对应的FilterField
+pub fn filter(st: &syn::DeriveInput, fields: &[FieldMeta]) -> proc_macro2::TokenStream {
+    let struct_name = &st.ident;
+    let vis = &st.vis;
+    let filter_name = format_ident!("{}Filter", struct_name);
+    let generics = &st.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let model = quote!(#struct_name #ty_generics);
+    let methods = fields.iter().map(|field| {
+        let FieldMeta {
+            ident,
+            name,
+            vis,
+            ty,
+            serializer,
+            ..
+        } = field;
+        let doc = format!("字段`{}`上的查询条件", name);
+        // 值按照字段的序列化方式转换为BSON
+        let (serialize, bound) = serializer.to_bson(ty);
+        quote! {
+            #[doc = #doc]
+            #vis fn #ident(self) -> axum_mongodb::FilterField<#model, #ty> #bound {
+                axum_mongodb::FilterField::new(#name, #serialize)
+            }
+        }
+    });
+    let doc = format!(
+        "`{}`的查询条件构建器，通过`{}::filter()`创建",
+        struct_name, struct_name
+    );
+    quote! {
+        #[doc = #doc]
+        #vis struct #filter_name #generics (::std::marker::PhantomData<fn() -> #model>) #where_clause;
+
+        impl #impl_generics #struct_name #ty_generics #where_clause {
+            /// 构建该模型的查询条件
+            pub fn filter() -> #filter_name #ty_generics {
+                #filter_name(::std::marker::PhantomData)
+            }
+        }
+
+        impl #impl_generics #filter_name #ty_generics #where_clause {
+            #(#methods)*
+        }
+    }
+}
@@ -17,7 +17,8 @@ fn change_args(st: &mut syn::ItemFn) {
         if let FnArg::Typed(pat_type) = item {
             if let syn::Type::Path(TypePath { path, .. }) = pat_type.ty.as_ref() {
                 if path.is_ident("DBServers") {
-                    pat_type.ty = syn::parse_str("MongoDbServer<crate::Servers>").unwrap();
+                    pat_type.ty =
+                        syn::parse_str("axum_mongodb::MongoDbServer<crate::Servers>").unwrap();
                 }
             }
         }
@@ -1,11 +1,18 @@
-use crate::{column_meta::ColumnMeta, COLUMN_META};
-use quote::quote;
-use syn::{Result, TypePath};
+use super::MainOptions;
+use quote::{quote, quote_spanned};
+use syn::{ext::IdentExt, spanned::Spanned, Result};
 
 // 生成Servers结构体里的内容
-pub fn inject_meta(st: &syn::ItemStruct) -> Result<proc_macro2::TokenStream> {
+pub fn inject_meta(
+    st: &syn::ItemStruct,
+    options: &MainOptions,
+) -> Result<proc_macro2::TokenStream> {
     let mut fields = proc_macro2::TokenStream::new();
     let mut fields_init = proc_macro2::TokenStream::new();
+    let mut models = proc_macro2::TokenStream::new();
+    let mut plans = proc_macro2::TokenStream::new();
+    let naming = options.naming.to_tokens();
+    let naming_index = options.naming.index();
     let struct_name = &st.ident;
     let vis = &st.vis;
     if !&st.fields.is_empty() {
@@ -14,30 +21,84 @@ pub fn inject_meta(st: &syn::ItemStruct) -> Result<proc_macro2::TokenStream> {
             "Struct must be empty",
         ));
     }
-    for (_, ColumnMeta { name, struct_name }) in unsafe { COLUMN_META.iter() } {
-        let field_name = syn::Ident::new(name, proc_macro2::Span::call_site());
-        let struct_type: TypePath = syn::parse_str(&struct_name)?;
+    // 模型由main上的models(...)显式声明，与各个Column宏的展开顺序无关
+    for model in options.models.iter() {
+        let field_name = model.field_name(options.naming)?;
+        let struct_type = &model.ty;
         fields.extend(quote!(
             pub #field_name:crate::Server<#struct_type>,
         ));
         fields_init.extend(quote!(
-            #field_name:crate::Server::new(db).await,
+            #field_name:<crate::Server<#struct_type> as axum_mongodb::NewWithDb>::new(db.clone()).await?,
         ));
+        plans.extend(quote!(
+            axum_mongodb::CollectionInit::plan(&crate::Server(
+                db.collection::<#struct_type>(<#struct_type as axum_mongodb::Column>::collection_name(#naming)),
+            ))
+            .await?,
+        ));
+        // 未使用Column宏的模型在此处报错
+        let mut assert_column = quote_spanned! {struct_type.span()=>
+            const _: fn() = || {
+                fn assert_column<T: axum_mongodb::Column>() {}
+                assert_column::<#struct_type>();
+            };
+        };
+        // 未通过models(field_name = Type)指定字段名时，字段名必须与集合名一致，
+        // 模型上的collection或naming与main上的命名策略不同时在此处报错
+        if model.field.is_none() {
+            let ident = &struct_type.path.segments.last().unwrap().ident;
+            let field = field_name.unraw().to_string();
+            let message = format!(
+                "the collection name of `{ident}` differs from its Servers field `{field}`, name the field explicitly with models(field_name = {ident})"
+            );
+            assert_column.extend(quote_spanned! {ident.span()=>
+                const _: () = ::std::assert!(
+                    axum_mongodb::__private::str_eq(
+                        <#struct_type as axum_mongodb::Column>::COLLECTION_NAMES[#naming_index],
+                        #field,
+                    ),
+                    #message,
+                );
+            });
+        }
+        models.extend(quote! {
+            #assert_column
+
+            impl axum_mongodb::HasModel<#struct_type> for #struct_name {
+                type Server = crate::Server<#struct_type>;
+                fn server(&self) -> &Self::Server {
+                    &self.#field_name
+                }
+            }
+        });
     }
 
     Ok(quote! {
+        #[allow(non_snake_case)]
         #[derive(Clone,Debug)]
         #vis struct #struct_name{
             #fields
         }
-        
-        #[axum_mongodb::async_trait]
+
+        #[axum_mongodb::__private::async_trait]
         impl axum_mongodb::NewWithDb for #struct_name{
-            async fn new(db:mongodb::Database)->Self{
-                #struct_name{
+            #[allow(unused_variables)]
+            async fn new(db:axum_mongodb::__private::mongodb::Database)->::std::result::Result<Self, axum_mongodb::InitError>{
+                Ok(#struct_name{
                     #fields_init
-                }
+                })
             }
         }
+
+        #[axum_mongodb::__private::async_trait]
+        impl axum_mongodb::PlanIndexes for #struct_name{
+            #[allow(unused_variables)]
+            async fn plan_indexes(db:axum_mongodb::__private::mongodb::Database)->::std::result::Result<Vec<axum_mongodb::IndexPlan>, axum_mongodb::InitError>{
+                Ok(vec![#plans])
+            }
+        }
+
+        #models
     })
 }
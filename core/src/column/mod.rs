@@ -1,44 +1,69 @@
-use crate::{
-    column_meta::{ColumnMeta, COLUMN_META},
-    indexes::Indexes,
-};
+use crate::{indexes::Indexes, naming::NamingStrategy};
 use quote::quote;
-use std::borrow::BorrowMut;
 use syn::Result;
+mod export_models;
+mod fields;
+mod filter;
 mod inject;
 mod inject_meta;
+mod model_id;
+mod options;
+mod patch;
+mod projection;
+mod update;
+pub use export_models::{export_models, servers, ExportModels, IncludeModels};
+pub use fields::{FieldMeta, Serializer};
 pub use inject::inject;
 pub use inject_meta::inject_meta;
+pub use options::{ColumnOptions, MainOptions, SyncMode};
+pub use projection::projection;
 
-// 收集元信息，并生成代码，实现Server<T>
-pub fn collect_meta(st: &syn::DeriveInput, drop: bool) -> Result<proc_macro2::TokenStream> {
+// 解析结构体元信息，并生成代码，实现Server<T>
+pub fn collect_meta(
+    st: &syn::DeriveInput,
+    drop: bool,
+    options: &ColumnOptions,
+) -> Result<proc_macro2::TokenStream> {
     let mut res = proc_macro2::TokenStream::new();
-    let mut field_index = Vec::new();
-    
-    let mut name = st.ident.to_string().to_lowercase();
-    name.push('s');
-    if let syn::Data::Struct(syn::DataStruct { fields, .. }) = &st.data {
-        unsafe {
-            match COLUMN_META.get(&name) {
-                Some(_) => {
-                    return Err(syn::Error::new_spanned(
-                        &st.ident,
-                        format!("Column {} already exists", &st.ident.to_string()),
-                    ))
+    let mut indexes_list = Vec::new();
+    let mut field_metas = Vec::new();
+
+    let struct_name = &st.ident;
+    let collection = options
+        .collection
+        .as_ref()
+        .map(|collection| collection.value());
+    if let syn::Data::Struct(syn::DataStruct {
+        fields: syn::Fields::Named(syn::FieldsNamed { named: fields, .. }),
+        ..
+    }) = &st.data
+    {
+        for attr in st.attrs.iter() {
+            if let Some(indexes) = Indexes::parse_from_struct_attr(attr)? {
+                indexes_list.push(indexes)
+            }
+        }
+        let rename_all = fields::parse_rename_all(&st.attrs)?;
+        for field in fields.iter() {
+            // 索引建立在文档中的字段名上，与serde的rename保持一致
+            let (field_name, serializer) = match fields::field_meta(field, rename_all)? {
+                Some(meta) => {
+                    let name = meta.name.clone();
+                    let serializer = meta.serializer.clone();
+                    field_metas.push(meta);
+                    (name, serializer)
                 }
-                None => {
-                    COLUMN_META
-                        .borrow_mut()
-                        .insert(name.clone(), ColumnMeta::new(name.clone(), st.ident.to_string()));
-                    for field in fields.iter() {
-                        let field_name = &field.ident.as_ref().unwrap().to_string();
-                        for attr in field.attrs.iter() {
-                            let indexes = Indexes::parse_from_attr(attr, field_name)?;
-                            if indexes.is_some() {
-                                field_index.push(indexes.unwrap().create_index())
-                            }
-                        }
-                    }
+                None => (
+                    field.ident.as_ref().unwrap().to_string(),
+                    Serializer::Default,
+                ),
+            };
+            let field_name = &field_name;
+            for attr in field.attrs.iter() {
+                if let Some(indexes) =
+                    Indexes::parse_from_attr(attr, field_name, &field.ty, &serializer)?
+                {
+                    indexes_list.push(indexes)
                 }
             }
         }
@@ -49,127 +74,108 @@ pub fn collect_meta(st: &syn::DeriveInput, drop: bool) -> Result<proc_macro2::To
         ));
     }
 
-    // 为Server<T>实现CollectionInit，在初始化时创建索引
-    let struct_name = &st.ident;
-    if drop {
-        res.extend(quote! {
-            #[axum_mongodb::async_trait]
-            impl axum_mongodb::CollectionInit for crate::Server<#struct_name> {
-                async fn init(&self) {
-                    self.drop_indexes(None).await.unwrap_or_else(|e|{
-                        tracing::error!("drop_indexes error: {:?}", e);
-                        ()
-                    });;
+    if let Some(collation) = &options.collation {
+        for indexes in indexes_list.iter_mut() {
+            indexes.set_default_collation(collation);
+        }
+    }
+    res.extend(fields::fields(st, &field_metas));
+    if !options.no_builders {
+        res.extend(filter::filter(st, &field_metas));
+        res.extend(update::update(st, &field_metas));
+    }
+    res.extend(model_id::model_id(st, &field_metas));
+    if options.patch {
+        res.extend(patch::patch(st, &field_metas));
+    }
+    // 嵌套的索引键在编译期检查，泛型模型在每个实例中分别检查
+    let field_index = Indexes::create_indexes(&indexes_list, &field_metas)?;
+    let (impl_generics, ty_generics, where_clause) = st.generics.split_for_impl();
 
-                    let doc_list = vec![#(#field_index),*];
-                    for item in doc_list {
-                        let mut options = mongodb::options::IndexOptions::default();
-                            if item.unique == true {
-                                options.unique = Some(true);
-                            }
-                            if item.name.is_some() {
-                                options.name = item.name.clone();
-                            }
-                            let create_index = self
-                                .create_index(
-                                    mongodb::IndexModel::builder()
-                                        .keys(item.keys)
-                                        .options(options)
-                                        .build(),
-                                    None,
-                                )
-                                .await;
-                            match create_index {
-                                Ok(_) => {
-                                    tracing::info!("create_index {:#?}", create_index);
-                                }
-                                Err(e) => {
-                                    tracing::error!("create_index error: {:#?}", e);
-                                }
-                            }
-                        }
-                }
-            }
-        });
-    } else {
-        res.extend(quote! {
-            #[axum_mongodb::async_trait]
-            impl axum_mongodb::CollectionInit for crate::Server<#struct_name> {
-                async fn init(&self) {
-                    use axum_mongodb::futures::TryStreamExt;
-                    let index = self.list_indexes(None).await;
-                    let doc_list:Vec<axum_mongodb::CreateIndexOptions> = vec![#(#field_index),*];
-                    match index {
-                        Ok(index) => {
-                            let index_list = index
-                                .try_collect::<Vec<_>>()
-                                .await
-                                .expect("collect index errored");
-                            tracing::info!("{:#?}", index_list);
+    // 为模型实现Column，运行时根据main上的默认命名策略获取集合名，索引也声明在模型上，
+    // 不依赖crate::Server，模型可以定义在其他crate中
+    let column_impl = |impl_generics: proc_macro2::TokenStream,
+                       ty: proc_macro2::TokenStream,
+                       where_clause: proc_macro2::TokenStream,
+                       name: &str| {
+        let collection_names = collection_names(collection.as_deref(), options.naming, name);
+        quote! {
+            impl #impl_generics axum_mongodb::Column for #ty #where_clause {
+                const COLLECTION_NAMES: [&'static str; 5] = [#(#collection_names),*];
 
-                            for item_name in &index_list {
-                                for doc_item in &doc_list {
-                                    if item_name.keys == doc_item.keys {
-                                        if item_name.options.is_some() {
-                                            self.drop_index(
-                                                item_name.options.clone().unwrap().name.as_ref().unwrap(),
-                                                None,
-                                            )
-                                            .await
-                                            .expect("drop index error");
-                                        }
-                                    }
-                                }
-                            }
+                fn indexes() -> Vec<axum_mongodb::CreateIndexOptions> {
+                    vec![#(#field_index),*]
+                }
 
-                            for item in doc_list {
-                                let mut options = mongodb::options::IndexOptions::default();
-                                if item.unique == true {
-                                    options.unique = Some(true);
-                                }
-                                if item.name.is_some() {
-                                    options.name = item.name.clone();
-                                }
-                                let create_index = self
-                                    .create_index(
-                                        mongodb::IndexModel::builder()
-                                            .keys(item.keys)
-                                            .options(options)
-                                            .build(),
-                                        None,
-                                    )
-                                    .await;
-                                tracing::info!("create_index {:#?}", create_index);
-                            }
-                        }
-                        Err(e) => {
-                            tracing::error!("{:#?}", e);
-                        }
-                    }
+                // 使用dropIndexes时删除未声明的索引
+                fn drop_undeclared() -> bool {
+                    #drop
                 }
             }
-        });
-    }
-    let name:syn::Ident=syn::parse_str(&name)?;
-    res.extend(quote!{
-        #[axum_mongodb::async_trait]
-        impl<S> axum::extract::FromRequestParts<S> for crate::Server<#struct_name>
-        where
-            S: Send + Sync,
-        {
-            type Rejection = std::convert::Infallible;
-            async fn from_request_parts(
-                parts: &mut axum::http::request::Parts,
-                _state: &S,
-            ) -> Result<Self, Self::Rejection> {
-                let dbs = parts
-                    .extensions
-                    .get::<axum_mongodb::MongoDbServer<crate::Servers>>()
-                    .expect("can not get MongoDbServer");
-                Ok(dbs.servers.#name.clone())
-            }
         }
-    });
+    };
+    if options.instances.is_empty() {
+        res.extend(column_impl(
+            quote!(#impl_generics),
+            quote!(#struct_name #ty_generics),
+            quote!(#where_clause),
+            &struct_name.to_string(),
+        ));
+    } else {
+        // 泛型模型的每个实例对应一个集合，集合名以及Servers中的字段名由实例名决定
+        let vis = &st.vis;
+        for (name, ty) in options.instances.iter() {
+            let doc = format!("`{}`的实例，对应一个单独的集合", quote!(#ty));
+            res.extend(quote! {
+                #[doc = #doc]
+                #vis type #name = #ty;
+            });
+            res.extend(column_impl(
+                quote!(),
+                quote!(#ty),
+                quote!(),
+                &name.to_string(),
+            ));
+        }
+    }
 
     Ok(res)
 }
+
+// 每种命名策略对应的集合名，指定了collection或naming时集合名固定
+fn collection_names(
+    collection: Option<&str>,
+    naming: Option<NamingStrategy>,
+    name: &str,
+) -> Vec<String> {
+    NamingStrategy::ALL
+        .iter()
+        .map(|default| match (collection, naming) {
+            (Some(collection), _) => collection.to_string(),
+            (None, Some(naming)) => naming.apply(name),
+            (None, None) => default.apply(name),
+        })
+        .collect()
+}
+
+// 为嵌套文档生成字段名常量以及{Model}Field枚举
+pub fn collect_fields(st: &syn::DeriveInput) -> Result<proc_macro2::TokenStream> {
+    let syn::Data::Struct(syn::DataStruct {
+        fields: syn::Fields::Named(syn::FieldsNamed { named, .. }),
+        ..
+    }) = &st.data
+    else {
+        return Err(syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "Only named struct is supported",
+        ));
+    };
+    let rename_all = fields::parse_rename_all(&st.attrs)?;
+    let mut field_metas: Vec<FieldMeta> = Vec::new();
+    for field in named.iter() {
+        if let Some(meta) = fields::field_meta(field, rename_all)? {
+            field_metas.push(meta);
+        }
+    }
+    Ok(fields::fields(st, &field_metas))
+}
@@ -0,0 +1,42 @@
+use super::fields::FieldMeta;
+use quote::quote;
+
+// 实现ModelId，_id的类型以及序列化方式由序列化为_id的字段决定，没有该字段时为ObjectId
+pub fn model_id(st: &syn::DeriveInput, fields: &[FieldMeta]) -> proc_macro2::TokenStream {
+    let struct_name = &st.ident;
+    let mut generics = st.generics.clone();
+    let (id, to_bson) = match fields.iter().find(|field| field.name == "_id") {
+        Some(field) => {
+            let ty = &field.ty;
+            let (serialize, _) = field.serializer.to_bson(ty);
+            if let Some(bound) = field.serializer.bound(ty) {
+                generics
+                    .make_where_clause()
+                    .predicates
+                    .push(syn::parse_quote!(#bound));
+            }
+            (quote!(#ty), quote!((#serialize)(id)))
+        }
+        None => (
+            quote!(axum_mongodb::__private::mongodb::bson::oid::ObjectId),
+            quote!(Ok(axum_mongodb::__private::mongodb::bson::Bson::ObjectId(
+                *id
+            ))),
+        ),
+    };
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    quote! {
+        impl #impl_generics axum_mongodb::ModelId for #struct_name #ty_generics #where_clause {
+            type Id = #id;
+
+            fn id_to_bson(
+                id: &Self::Id,
+            ) -> ::std::result::Result<
+                axum_mongodb::__private::mongodb::bson::Bson,
+                axum_mongodb::__private::mongodb::bson::ser::Error,
+            > {
+                #to_bson
+            }
+        }
+    }
+}
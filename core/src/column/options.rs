@@ -0,0 +1,244 @@
+use crate::{indexes::Collation, naming::NamingStrategy};
+use syn::{ext::IdentExt, parse::Parse, LitStr, Result, Token};
+
+// 结构体上的#[column(...)]属性
+#[derive(Debug, Default)]
+pub struct ColumnOptions {
+    pub collection: Option<LitStr>,
+    pub naming: Option<NamingStrategy>,
+    pub collation: Option<Collation>,
+    // 泛型模型的实例，例如instances(OrderEvent = Event<OrderPlaced>)
+    pub instances: Vec<(syn::Ident, syn::Type)>,
+    // 是否生成{Model}Patch
+    pub patch: bool,
+    // 不生成{Model}Filter、{Model}Update以及Model::filter()、Model::update()
+    pub no_builders: bool,
+}
+
+impl ColumnOptions {
+    pub fn parse_attrs(attrs: &[syn::Attribute]) -> Result<Self> {
+        let mut options = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("column")) {
+            options.parse_attr(attr)?;
+        }
+        if let (Some(collection), Some(_)) = (&options.collection, &options.naming) {
+            return Err(syn::Error::new_spanned(
+                collection,
+                "collection and naming cannot be used together",
+            ));
+        }
+        // 每个实例对应一个集合，不能共用同一个集合名
+        if let (Some(collection), false) = (&options.collection, options.instances.is_empty()) {
+            return Err(syn::Error::new_spanned(
+                collection,
+                "collection and instances cannot be used together",
+            ));
+        }
+        // {Model}Patch转换为{Model}Update
+        if options.patch && options.no_builders {
+            return Err(syn::Error::new(
+                proc_macro2::Span::call_site(),
+                "patch and no_builders cannot be used together",
+            ));
+        }
+        Ok(options)
+    }
+
+    fn parse_attr(&mut self, attr: &syn::Attribute) -> Result<()> {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("collection") {
+                meta.input.parse::<Token![=]>()?;
+                self.collection = Some(meta.input.parse::<LitStr>()?);
+                return Ok(());
+            }
+            if meta.path.is_ident("naming") {
+                meta.input.parse::<Token![=]>()?;
+                let value = meta.input.parse::<LitStr>()?;
+                self.naming = Some(NamingStrategy::parse(&value)?);
+                return Ok(());
+            }
+            if meta.path.is_ident("instances") {
+                let content;
+                syn::parenthesized!(content in meta.input);
+                let instances = content.parse_terminated(
+                    |input| {
+                        let name = input.parse::<syn::Ident>()?;
+                        input.parse::<Token![=]>()?;
+                        Ok((name, input.parse::<syn::Type>()?))
+                    },
+                    Token![,],
+                )?;
+                self.instances.extend(instances);
+                return Ok(());
+            }
+            if meta.path.is_ident("patch") {
+                self.patch = true;
+                return Ok(());
+            }
+            if meta.path.is_ident("no_builders") {
+                self.no_builders = true;
+                return Ok(());
+            }
+            // 所有索引默认的排序规则
+            if meta.path.is_ident("collation") {
+                self.collation = Some(Collation::parse_meta(&meta)?);
+                return Ok(());
+            }
+            Err(meta.error("unsupported column attribute"))
+        })
+    }
+}
+
+// #[axum_mongodb::main(...)]与#[axum_mongodb::inject_meta(...)]上的属性
+#[derive(Debug, Default, Clone)]
+pub struct MainOptions {
+    pub naming: NamingStrategy,
+    pub models: Vec<ModelEntry>,
+    pub sync_mode: SyncMode,
+    // 其他crate通过export_models!导出的模型列表
+    pub includes: Vec<syn::Path>,
+}
+
+impl MainOptions {
+    pub fn parse_meta(&mut self, meta: syn::meta::ParseNestedMeta) -> Result<()> {
+        if meta.path.is_ident("naming") {
+            meta.input.parse::<Token![=]>()?;
+            let value = meta.input.parse::<LitStr>()?;
+            self.naming = NamingStrategy::parse(&value)?;
+            return Ok(());
+        }
+        if meta.path.is_ident("sync_mode") {
+            meta.input.parse::<Token![=]>()?;
+            let value = meta.input.parse::<LitStr>()?;
+            self.sync_mode = SyncMode::parse(&value)?;
+            return Ok(());
+        }
+        if meta.path.is_ident("models") {
+            let content;
+            syn::parenthesized!(content in meta.input);
+            let models = content.parse_terminated(ModelEntry::parse, Token![,])?;
+            self.models.extend(models);
+            return Ok(());
+        }
+        if meta.path.is_ident("include") {
+            let content;
+            syn::parenthesized!(content in meta.input);
+            let includes = content.parse_terminated(syn::Path::parse_mod_style, Token![,])?;
+            self.includes.extend(includes);
+            return Ok(());
+        }
+        Err(meta.error("unsupported main attribute"))
+    }
+}
+
+// 转换回属性的写法，用于传递给inject_meta以及include中的宏
+impl quote::ToTokens for MainOptions {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let naming = self.naming.name();
+        let sync_mode = self.sync_mode.name();
+        let models = &self.models;
+        let includes = &self.includes;
+        tokens.extend(quote::quote! {
+            naming = #naming,
+            sync_mode = #sync_mode,
+            models(#(#models),*),
+            include(#(#includes),*)
+        });
+    }
+}
+
+// 初始化时索引的同步模式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SyncMode {
+    #[default]
+    Apply,
+    Plan,
+    Off,
+}
+
+impl SyncMode {
+    pub fn parse(value: &LitStr) -> Result<Self> {
+        match value.value().as_str() {
+            "apply" => Ok(Self::Apply),
+            "plan" => Ok(Self::Plan),
+            "off" => Ok(Self::Off),
+            _ => Err(syn::Error::new_spanned(
+                value,
+                "sync_mode must be \"apply\", \"plan\" or \"off\"",
+            )),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Apply => "apply",
+            Self::Plan => "plan",
+            Self::Off => "off",
+        }
+    }
+
+    pub fn to_tokens(self) -> proc_macro2::TokenStream {
+        match self {
+            Self::Apply => quote::quote!(axum_mongodb::SyncMode::Apply),
+            Self::Plan => quote::quote!(axum_mongodb::SyncMode::Plan),
+            Self::Off => quote::quote!(axum_mongodb::SyncMode::Off),
+        }
+    }
+}
+
+// models(...)中的一项，可以是`Todo`，也可以通过`todo_list = Todo`指定Servers中的字段名
+#[derive(Debug, Clone)]
+pub struct ModelEntry {
+    pub field: Option<syn::Ident>,
+    pub ty: syn::TypePath,
+}
+
+impl ModelEntry {
+    // Servers中的字段名，未指定时对类型名应用命名策略，得到关键字时使用原始标识符，例如Type得到r#type
+    pub fn field_name(&self, naming: NamingStrategy) -> Result<syn::Ident> {
+        let Some(field) = &self.field else {
+            let ident = &self.ty.path.segments.last().unwrap().ident;
+            let name = naming.apply(&ident.unraw().to_string());
+            if syn::parse_str::<syn::Ident>(&name).is_ok() {
+                return Ok(syn::Ident::new(&name, ident.span()));
+            }
+            // self、super、crate、Self不能作为原始标识符
+            if !["self", "super", "crate", "Self"].contains(&name.as_str()) {
+                return Ok(syn::Ident::new_raw(&name, ident.span()));
+            }
+            return Err(syn::Error::new_spanned(
+                &self.ty,
+                format!(
+                    "`{name}` cannot be used as a Servers field name, name it explicitly with models(field_name = {ident})"
+                ),
+            ));
+        };
+        Ok(field.clone())
+    }
+}
+
+impl Parse for ModelEntry {
+    fn parse(input: syn::parse::ParseStream) -> Result<Self> {
+        let field = if input.peek(syn::Ident) && input.peek2(Token![=]) {
+            let field = input.parse::<syn::Ident>()?;
+            input.parse::<Token![=]>()?;
+            Some(field)
+        } else {
+            None
+        };
+        Ok(Self {
+            field,
+            ty: input.parse()?,
+        })
+    }
+}
+
+impl quote::ToTokens for ModelEntry {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let ty = &self.ty;
+        match &self.field {
+            Some(field) => tokens.extend(quote::quote!(#field = #ty)),
+            None => ty.to_tokens(tokens),
+        }
+    }
+}
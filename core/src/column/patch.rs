@@ -0,0 +1,72 @@
+use super::{fields::FieldMeta, update::option_inner};
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+
+// 为模型生成{Model}Patch，每个字段都是Option，into_update只为存在的字段生成$set
+pub fn patch(st: &syn::DeriveInput, fields: &[FieldMeta]) -> proc_macro2::TokenStream {
+    let struct_name = &st.ident;
+    let vis = &st.vis;
+    let patch_name = format_ident!("{}Patch", struct_name);
+    let update_name = format_ident!("{}Update", struct_name);
+    let generics = &st.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let fields = fields
+        .iter()
+        .filter(|field| field.is_mutable())
+        .collect::<Vec<_>>();
+    let patch_fields = fields.iter().map(|field| {
+        let FieldMeta {
+            ident,
+            name,
+            vis,
+            ty,
+            ..
+        } = field;
+        // Option字段中null表示设置为null，与字段不存在区分开
+        let deserialize_with = if option_inner(ty).is_some() {
+            quote!(deserialize_with = "axum_mongodb::__private::deserialize_some",)
+        } else {
+            quote!()
+        };
+        quote! {
+            #[serde(default, rename = #name, #deserialize_with)]
+            #vis #ident: Option<#ty>
+        }
+    });
+    let sets = fields.iter().map(|field| {
+        let ident = &field.ident;
+        let set = format_ident!("set_{}", ident.unraw());
+        quote! {
+            if let Some(value) = self.#ident {
+                update = update.#set(value);
+            }
+        }
+    });
+    let bounds = fields
+        .iter()
+        .filter_map(|field| field.serializer.bound(&field.ty));
+    let doc = format!(
+        "`{}`的部分更新，所有字段都是可选的，通过`into_update`转换为只包含存在字段的`$set`",
+        struct_name
+    );
+    quote! {
+        #[doc = #doc]
+        #[derive(Debug, Clone, Default, axum_mongodb::__private::serde::Deserialize)]
+        #[serde(crate = "axum_mongodb::__private::serde")]
+        #vis struct #patch_name #generics #where_clause {
+            #(#patch_fields),*
+        }
+
+        impl #impl_generics #patch_name #ty_generics #where_clause {
+            /// 转换为更新操作，可以继续追加其他更新
+            pub fn into_update(self) -> #update_name #ty_generics
+            where
+                #(#bounds),*
+            {
+                let mut update = #struct_name::update();
+                #(#sets)*
+                update
+            }
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use super::fields::{self, FieldMeta};
+use quote::quote;
+use syn::{ext::IdentExt, Result, Token};
+
+// #[projection(of = Todo)]
+fn parse_model(st: &syn::DeriveInput) -> Result<syn::Type> {
+    let mut model = None;
+    for attr in st
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("projection"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("of") {
+                meta.input.parse::<Token![=]>()?;
+                model = Some(meta.input.parse::<syn::Type>()?);
+                return Ok(());
+            }
+            Err(meta.error("unsupported projection attribute"))
+        })?;
+    }
+    model.ok_or_else(|| {
+        syn::Error::new_spanned(&st.ident, "missing model, e.g. #[projection(of = Todo)]")
+    })
+}
+
+// 为投影类型实现Projection，并在编译期检查每个字段在模型中存在、类型兼容且序列化后的字段名一致
+pub fn projection(st: &syn::DeriveInput) -> Result<proc_macro2::TokenStream> {
+    let syn::Data::Struct(syn::DataStruct {
+        fields: syn::Fields::Named(syn::FieldsNamed { named, .. }),
+        ..
+    }) = &st.data
+    else {
+        return Err(syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "Only named struct is supported",
+        ));
+    };
+    let model = parse_model(st)?;
+    let rename_all = fields::parse_rename_all(&st.attrs)?;
+    let mut field_metas: Vec<FieldMeta> = Vec::new();
+    for field in named.iter() {
+        if let Some(meta) = fields::field_meta(field, rename_all)? {
+            field_metas.push(meta);
+        }
+    }
+    let struct_name = &st.ident;
+    let (impl_generics, ty_generics, where_clause) = st.generics.split_for_impl();
+    let names = field_metas.iter().map(|field| &field.name);
+    // 未投影_id时显式排除，MongoDB默认会返回_id
+    let exclude_id = if field_metas.iter().any(|field| field.name == "_id") {
+        quote!()
+    } else {
+        quote!("_id": 0,)
+    };
+    let types = field_metas.iter().map(|field| {
+        let ident = &field.ident;
+        let ty = &field.ty;
+        quote!(axum_mongodb::ProjectionField::new(|model: &#model| &model.#ident).check::<#ty>();)
+    });
+    let names_checks = field_metas.iter().map(|field| {
+        let ident = field.ident.unraw();
+        let name = &field.name;
+        let message = format!(
+            "field `{}` of `{}` must be serialized with the same name as the field of `{}`",
+            ident,
+            struct_name,
+            quote!(#model)
+        );
+        let ident = ident.to_string();
+        quote! {
+            assert!(
+                match axum_mongodb::__private::nested_field_name::<#model>(#ident) {
+                    Some(model_name) => axum_mongodb::__private::str_eq(model_name, #name),
+                    None => false,
+                },
+                #message
+            );
+        }
+    });
+    Ok(quote! {
+        impl #impl_generics axum_mongodb::Projection for #struct_name #ty_generics #where_clause {
+            type Model = #model;
+
+            fn projection() -> axum_mongodb::__private::mongodb::bson::Document {
+                axum_mongodb::__private::doc! {
+                    #exclude_id
+                    #(#names: 1),*
+                }
+            }
+        }
+
+        const _: () = {
+            #[allow(dead_code)]
+            fn check_types #impl_generics () #where_clause {
+                #(#types)*
+            }
+            #(#names_checks)*
+        };
+    })
+}
@@ -0,0 +1,180 @@
+use super::fields::FieldMeta;
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+
+// 为模型生成{Model}Update，Model::update().set_field(..)构建更新操作
+pub fn update(st: &syn::DeriveInput, fields: &[FieldMeta]) -> proc_macro2::TokenStream {
+    let struct_name = &st.ident;
+    let vis = &st.vis;
+    let update_name = format_ident!("{}Update", struct_name);
+    let generics = &st.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let model = quote!(#struct_name #ty_generics);
+    // _id以及#[column(immutable)]的字段在文档创建后不能修改
+    let methods = fields
+        .iter()
+        .filter(|field| field.is_mutable())
+        .map(|field| {
+            let FieldMeta {
+                ident,
+                name,
+                vis,
+                ty,
+                serializer,
+                ..
+            } = field;
+            let field = ident.unraw();
+            let (serialize, bound) = serializer.to_bson(ty);
+            let set = format_ident!("set_{}", field);
+            let doc = format!("设置字段`{}`，对应`$set`", name);
+            let mut methods = quote! {
+                #[doc = #doc]
+                #vis fn #set(self, value: impl axum_mongodb::FieldValue<#ty>) -> Self #bound {
+                    Self(self.0.set(#name, axum_mongodb::FieldValue::into_value(value), #serialize))
+                }
+            };
+            // 只有Option字段可以删除，否则文档无法再反序列化为模型
+            let inner = option_inner(ty);
+            if inner.is_some() {
+                let unset = format_ident!("unset_{}", field);
+                let doc = format!("删除字段`{}`，对应`$unset`", name);
+                methods.extend(quote! {
+                    #[doc = #doc]
+                    #vis fn #unset(self) -> Self {
+                        Self(self.0.unset(#name))
+                    }
+                });
+            }
+            // inc_与push_的值同样按照字段的序列化方式转换，Option字段包装为Some后序列化
+            let wrap = |value| match inner {
+                Some(_) => quote!(::std::option::Option::Some(#value)),
+                None => value,
+            };
+            let value_ty = inner.unwrap_or(ty);
+            if is_numeric(value_ty) {
+                let inc = format_ident!("inc_{}", field);
+                let doc = format!("字段`{}`增加指定的值，对应`$inc`，多次调用时增量相加", name);
+                let value = wrap(quote!(axum_mongodb::FieldValue::into_value(value)));
+                methods.extend(quote! {
+                    #[doc = #doc]
+                    #vis fn #inc(self, value: impl axum_mongodb::FieldValue<#value_ty>) -> Self #bound {
+                        Self(self.0.inc(#name, #value, #serialize))
+                    }
+                });
+            }
+            if let Some(element) = vec_element(value_ty) {
+                let push = format_ident!("push_{}", field);
+                let doc = format!("向数组字段`{}`中追加元素，对应`$push`，多次调用时合并到`$each`中", name);
+                let value = wrap(quote!(::std::vec![axum_mongodb::FieldValue::into_value(value)]));
+                methods.extend(quote! {
+                    #[doc = #doc]
+                    #vis fn #push(self, value: impl axum_mongodb::FieldValue<#element>) -> Self #bound {
+                        Self(self.0.push(#name, #value, #serialize))
+                    }
+                });
+            }
+            methods
+        });
+    let doc = format!(
+        "`{}`的更新操作构建器，通过`{}::update()`创建",
+        struct_name, struct_name
+    );
+    quote! {
+        #[doc = #doc]
+        #vis struct #update_name #generics (axum_mongodb::Update<#model>) #where_clause;
+
+        impl #impl_generics #struct_name #ty_generics #where_clause {
+            /// 构建该模型的更新操作
+            pub fn update() -> #update_name #ty_generics {
+                #update_name(axum_mongodb::Update::new())
+            }
+        }
+
+        impl #impl_generics #update_name #ty_generics #where_clause {
+            #(#methods)*
+
+            /// 是否没有任何更新操作
+            pub fn is_empty(&self) -> bool {
+                self.0.is_empty()
+            }
+
+            /// 转换为更新文档，值无法序列化时返回第一个错误
+            pub fn into_document(self) -> ::std::result::Result<axum_mongodb::__private::mongodb::bson::Document, axum_mongodb::ValueError> {
+                self.0.into_document()
+            }
+        }
+
+        impl #impl_generics Clone for #update_name #ty_generics #where_clause {
+            fn clone(&self) -> Self {
+                Self(self.0.clone())
+            }
+        }
+
+        impl #impl_generics ::std::fmt::Debug for #update_name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                ::std::fmt::Debug::fmt(&self.0, f)
+            }
+        }
+
+        impl #impl_generics From<#update_name #ty_generics> for axum_mongodb::Update<#model> #where_clause {
+            fn from(update: #update_name #ty_generics) -> Self {
+                update.0
+            }
+        }
+
+        impl #impl_generics TryFrom<#update_name #ty_generics> for axum_mongodb::__private::mongodb::bson::Document #where_clause {
+            type Error = axum_mongodb::ValueError;
+
+            fn try_from(update: #update_name #ty_generics) -> ::std::result::Result<Self, Self::Error> {
+                update.0.try_into()
+            }
+        }
+
+        impl #impl_generics TryFrom<#update_name #ty_generics> for axum_mongodb::__private::mongodb::options::UpdateModifications #where_clause {
+            type Error = axum_mongodb::ValueError;
+
+            fn try_from(update: #update_name #ty_generics) -> ::std::result::Result<Self, Self::Error> {
+                update.0.try_into()
+            }
+        }
+    }
+}
+
+// Option<T>中的T
+pub fn option_inner(ty: &syn::Type) -> Option<&syn::Type> {
+    generic_argument(ty, "Option")
+}
+
+// Vec<T>中的T
+fn vec_element(ty: &syn::Type) -> Option<&syn::Type> {
+    generic_argument(ty, "Vec")
+}
+
+fn generic_argument<'a>(ty: &'a syn::Type, name: &str) -> Option<&'a syn::Type> {
+    let syn::Type::Path(syn::TypePath { qself: None, path }) = ty else {
+        return None;
+    };
+    let segment = path.segments.last()?;
+    if segment.ident != name {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        syn::GenericArgument::Type(inner) => Some(inner),
+        _ => None,
+    }
+}
+
+fn is_numeric(ty: &syn::Type) -> bool {
+    let syn::Type::Path(syn::TypePath { qself: None, path }) = ty else {
+        return false;
+    };
+    path.get_ident().is_some_and(|ident| {
+        [
+            "i8", "i16", "i32", "i64", "isize", "u8", "u16", "u32", "u64", "usize", "f32", "f64",
+        ]
+        .contains(&ident.to_string().as_str())
+    })
+}
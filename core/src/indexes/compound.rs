@@ -1,12 +1,19 @@
+use super::{
+    key_path::KeyPath,
+    options::{IndexOptions, IndexOrder},
+};
+use crate::column::FieldMeta;
 use quote::quote;
-use syn::{parse::Parse, LitStr, Result, Token};
+use syn::{LitStr, Result, Token};
 
 #[derive(Debug)]
 pub struct CompoundIndex {
     pub name: Option<String>,
     pub unique: bool,
     pub field_name: String,
-    pub other_fields: Vec<String>,
+    pub order: IndexOrder,
+    pub options: IndexOptions,
+    pub other_fields: Vec<(KeyPath, IndexOrder)>,
 }
 
 impl CompoundIndex {
@@ -15,38 +22,52 @@ impl CompoundIndex {
             name: None,
             unique: None,
             field_name: None,
+            order: None,
+            options: IndexOptions::default(),
             other_fields: Vec::new(),
         }
     }
 
-    pub fn create_index(&self) -> proc_macro2::TokenStream {
+    // other_fields与#[index]的键一样根据fields转换为文档中的字段名
+    pub fn create_index(&self, fields: &[FieldMeta]) -> Result<proc_macro2::TokenStream> {
         let field_name = &self.field_name;
         let name = &self.name;
         let unique = self.unique;
-        let other_fields = &self.other_fields;
-        if self.name.is_some() {
+        let order = self.order;
+        let options = self.options.create_fields();
+        let other_fields = self
+            .other_fields
+            .iter()
+            .map(|(field, _)| field.resolve(fields))
+            .collect::<Result<Vec<_>>>()?;
+        let other_orders = self.other_fields.iter().map(|(_, order)| order);
+        Ok(if self.name.is_some() {
             quote! {
                 axum_mongodb::CreateIndexOptions{
-                    keys:doc!{
-                        #field_name:1,
-                        #(#other_fields:1),*
+                    keys:axum_mongodb::__private::doc!{
+                        #field_name:#order,
+                        #(#other_fields:#other_orders),*
                     },
                     name:Some(#name.to_string()),
                     unique:#unique,
+                    #options
+                    ..Default::default()
                 }
             }
         } else {
             quote! {
                 axum_mongodb::CreateIndexOptions{
-                    keys:doc!{
-                        #field_name:1,
-                        #(#other_fields:1),*
+                    keys:axum_mongodb::__private::doc!{
+                        #field_name:#order,
+                        #(#other_fields:#other_orders),*
                     },
                     name:None,
                     unique:#unique,
+                    #options
+                    ..Default::default()
                 }
             }
-        }
+        })
     }
 }
 
@@ -54,7 +75,9 @@ pub struct CompoundIndexBuilder {
     pub name: Option<String>,
     pub unique: Option<bool>,
     pub field_name: Option<String>,
-    pub other_fields: Vec<String>,
+    pub order: Option<IndexOrder>,
+    pub options: IndexOptions,
+    pub other_fields: Vec<(KeyPath, IndexOrder)>,
 }
 
 impl CompoundIndexBuilder {
@@ -64,6 +87,9 @@ impl CompoundIndexBuilder {
     }
     pub fn parse_attr(&mut self, attr: &syn::Attribute) -> Result<&mut Self> {
         attr.parse_nested_meta(|meta| {
+            if self.options.parse_meta(&meta)? {
+                return Ok(());
+            }
             if meta.path.is_ident("unique") {
                 self.unique = Some(true);
                 return Ok(());
@@ -74,16 +100,51 @@ impl CompoundIndexBuilder {
                 self.name = Some(value.value());
                 return Ok(());
             }
+            if meta.path.is_ident("order") {
+                self.order = Some(IndexOrder::parse_meta(&meta)?);
+                return Ok(());
+            }
+            // other_fields(name(desc), "address.city", age)，未指定方向时为升序
             if meta.path.is_ident("other_fields") {
-                let count;
-                syn::parenthesized!(count in meta.input);
-                let fields = count.parse_terminated(syn::Ident::parse, Token![,])?;
-                for field in fields {
-                    self.other_fields.push(field.to_string());
-                }
+                let content;
+                syn::parenthesized!(content in meta.input);
+                let fields = content.parse_terminated(
+                    |input| {
+                        let field = KeyPath::parse(input)?;
+                        let mut order = IndexOrder::Asc;
+                        if input.peek(syn::token::Paren) {
+                            let content;
+                            syn::parenthesized!(content in input);
+                            order = IndexOrder::parse_ident(&content.parse()?)?;
+                        }
+                        Ok((field, order))
+                    },
+                    Token![,],
+                )?;
+                self.other_fields.extend(fields);
+                return Ok(());
             }
-            return Ok(());
+            Err(meta.error("unsupported compoundIndex attribute"))
         })?;
+        let hashed = self
+            .other_fields
+            .iter()
+            .map(|(_, order)| order)
+            .chain(self.order.as_ref())
+            .filter(|order| **order == IndexOrder::Hashed)
+            .count();
+        if hashed > 1 {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "compound index can only contain one hashed field",
+            ));
+        }
+        if hashed == 1 && self.unique == Some(true) {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "hashed index can not be unique",
+            ));
+        }
         Ok(self)
     }
     pub fn build(&self) -> CompoundIndex {
@@ -91,6 +152,8 @@ impl CompoundIndexBuilder {
             name: self.name.clone(),
             unique: self.unique.unwrap_or(false),
             field_name: self.field_name.clone().expect("field_name is required"),
+            order: self.order.unwrap_or_default(),
+            options: self.options.clone(),
             other_fields: self.other_fields.clone(),
         }
     }
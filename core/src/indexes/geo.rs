@@ -0,0 +1,211 @@
+use super::{key_path::KeyPath, options::IndexOptions};
+use crate::column::FieldMeta;
+use quote::quote;
+use syn::{LitInt, LitStr, Result, Token};
+
+// 地理空间索引，支持2dsphere以及2d
+#[derive(Debug)]
+pub struct GeoIndex {
+    pub name: Option<String>,
+    pub unique: bool,
+    pub field_name: String,
+    pub options: IndexOptions,
+    pub kind: String,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub bits: Option<u32>,
+    pub other_fields: Vec<KeyPath>,
+}
+
+impl GeoIndex {
+    pub fn supports_collation(&self) -> bool {
+        self.kind != "2d"
+    }
+
+    pub fn builder() -> GeoIndexBuilder {
+        GeoIndexBuilder {
+            name: None,
+            unique: None,
+            field_name: None,
+            options: IndexOptions::default(),
+            kind: None,
+            min: None,
+            max: None,
+            bits: None,
+            other_fields: Vec::new(),
+        }
+    }
+
+    // other_fields与#[index]的键一样根据fields转换为文档中的字段名
+    pub fn create_index(&self, fields: &[FieldMeta]) -> Result<proc_macro2::TokenStream> {
+        let field_name = &self.field_name;
+        let options = self.options.create_fields();
+        let unique = self.unique;
+        let kind = &self.kind;
+        let other_fields = self
+            .other_fields
+            .iter()
+            .map(|field| field.resolve(fields))
+            .collect::<Result<Vec<_>>>()?;
+        let name = match &self.name {
+            Some(name) => quote!(Some(#name.to_string())),
+            None => quote!(None),
+        };
+        let min = option_tokens(self.min);
+        let max = option_tokens(self.max);
+        let bits = option_tokens(self.bits);
+        Ok(quote! {
+            axum_mongodb::CreateIndexOptions{
+                keys:axum_mongodb::__private::doc!{
+                    #field_name:#kind,
+                    #(#other_fields:1),*
+                },
+                name:#name,
+                unique:#unique,
+                min:#min,
+                max:#max,
+                bits:#bits,
+                #options
+                ..Default::default()
+            }
+        })
+    }
+}
+
+fn option_tokens<T: quote::ToTokens>(value: Option<T>) -> proc_macro2::TokenStream {
+    match value {
+        Some(value) => quote!(Some(#value)),
+        None => quote!(None),
+    }
+}
+
+pub struct GeoIndexBuilder {
+    pub name: Option<String>,
+    pub unique: Option<bool>,
+    pub field_name: Option<String>,
+    pub options: IndexOptions,
+    pub kind: Option<LitStr>,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub bits: Option<u32>,
+    pub other_fields: Vec<KeyPath>,
+}
+
+impl GeoIndexBuilder {
+    pub fn set_field_name(&mut self, field_name: &str) -> &mut Self {
+        self.field_name = Some(field_name.to_string());
+        self
+    }
+    pub fn parse_attr(&mut self, attr: &syn::Attribute) -> Result<&mut Self> {
+        // 允许不带参数的#[geoIndex]，默认为2dsphere
+        if matches!(attr.meta, syn::Meta::Path(_)) {
+            return Ok(self);
+        }
+        attr.parse_nested_meta(|meta| {
+            if self.options.parse_meta(&meta)? {
+                return Ok(());
+            }
+            if meta.path.is_ident("unique") {
+                self.unique = Some(true);
+                return Ok(());
+            }
+            if meta.path.is_ident("name") {
+                meta.input.parse::<Token![=]>()?;
+                let value = meta.input.parse::<LitStr>()?;
+                self.name = Some(value.value());
+                return Ok(());
+            }
+            if meta.path.is_ident("kind") {
+                meta.input.parse::<Token![=]>()?;
+                let value = meta.input.parse::<LitStr>()?;
+                if value.value() != "2dsphere" && value.value() != "2d" {
+                    return Err(syn::Error::new_spanned(
+                        value,
+                        "geoIndex kind must be \"2dsphere\" or \"2d\"",
+                    ));
+                }
+                self.kind = Some(value);
+                return Ok(());
+            }
+            if meta.path.is_ident("min") {
+                meta.input.parse::<Token![=]>()?;
+                self.min = Some(parse_f64(meta.input)?);
+                return Ok(());
+            }
+            if meta.path.is_ident("max") {
+                meta.input.parse::<Token![=]>()?;
+                self.max = Some(parse_f64(meta.input)?);
+                return Ok(());
+            }
+            if meta.path.is_ident("bits") {
+                meta.input.parse::<Token![=]>()?;
+                let value = meta.input.parse::<LitInt>()?;
+                let bits = value.base10_parse::<u32>()?;
+                if !(1..=32).contains(&bits) {
+                    return Err(syn::Error::new_spanned(
+                        value,
+                        "bits must be between 1 and 32",
+                    ));
+                }
+                self.bits = Some(bits);
+                return Ok(());
+            }
+            if meta.path.is_ident("other_fields") {
+                let count;
+                syn::parenthesized!(count in meta.input);
+                let fields = count.parse_terminated(KeyPath::parse, Token![,])?;
+                self.other_fields.extend(fields);
+                return Ok(());
+            }
+            Err(meta.error("unsupported geoIndex attribute"))
+        })?;
+        // min、max、bits只适用于2d索引
+        let is_2d = self.kind.as_ref().is_some_and(|kind| kind.value() == "2d");
+        if !is_2d && (self.min.is_some() || self.max.is_some() || self.bits.is_some()) {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "min, max and bits are only supported by kind = \"2d\"",
+            ));
+        }
+        if is_2d && self.options.collation.is_some() {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "kind = \"2d\" does not support collation",
+            ));
+        }
+        if let (Some(min), Some(max)) = (self.min, self.max) {
+            if min >= max {
+                return Err(syn::Error::new_spanned(attr, "min must be less than max"));
+            }
+        }
+        Ok(self)
+    }
+    pub fn build(&self) -> GeoIndex {
+        GeoIndex {
+            name: self.name.clone(),
+            unique: self.unique.unwrap_or(false),
+            field_name: self.field_name.clone().expect("field_name is required"),
+            options: self.options.clone(),
+            kind: self
+                .kind
+                .as_ref()
+                .map(|kind| kind.value())
+                .unwrap_or_else(|| "2dsphere".to_string()),
+            min: self.min,
+            max: self.max,
+            bits: self.bits,
+            other_fields: self.other_fields.clone(),
+        }
+    }
+}
+
+// 解析带符号的数字，例如-180或180.0
+fn parse_f64(input: syn::parse::ParseStream) -> Result<f64> {
+    let neg = input.parse::<Option<Token![-]>>()?.is_some();
+    let value = match input.parse::<syn::Lit>()? {
+        syn::Lit::Int(value) => value.base10_parse::<f64>()?,
+        syn::Lit::Float(value) => value.base10_parse::<f64>()?,
+        lit => return Err(syn::Error::new_spanned(lit, "expected number")),
+    };
+    Ok(if neg { -value } else { value })
+}
@@ -0,0 +1,118 @@
+use crate::column::FieldMeta;
+use quote::{quote, quote_spanned};
+use syn::{ext::IdentExt, parse::ParseStream, LitStr, Result};
+
+// 索引中的字段路径，使用Rust字段名，例如tenant_id或"address.city"，
+// 生成索引时转换为文档中的字段名，与serde的rename以及rename_all保持一致
+#[derive(Debug, Clone)]
+pub struct KeyPath {
+    pub segments: Vec<syn::Ident>,
+    pub span: proc_macro2::Span,
+}
+
+impl KeyPath {
+    pub fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(LitStr) {
+            let path = input.parse::<LitStr>()?;
+            let segments = path
+                .value()
+                .split('.')
+                .map(|field| field_ident(field, path.span()))
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(|| syn::Error::new_spanned(&path, "invalid index key path"))?;
+            Ok(Self {
+                segments,
+                span: path.span(),
+            })
+        } else {
+            // 与parse_nested_meta一致，关键字也可以作为字段名
+            Ok(input.call(syn::Ident::parse_any)?.into())
+        }
+    }
+
+    // 按照Rust字段名比较，用于检查重复的键
+    pub fn is_same(&self, other: &Self) -> bool {
+        self.segments.len() == other.segments.len()
+            && self
+                .segments
+                .iter()
+                .zip(other.segments.iter())
+                .all(|(a, b)| a.unraw() == b.unraw())
+    }
+
+    // 文档中的字段路径，第一段在展开时根据模型的字段解析，嵌套文档中的字段在编译期通过NestedFields解析，
+    // 经过Option与Vec时使用元素类型中的字段
+    pub fn resolve(&self, fields: &[FieldMeta]) -> Result<proc_macro2::TokenStream> {
+        let path = self.to_string();
+        let first = &self.segments[0];
+        let Some(field) = fields
+            .iter()
+            .find(|field| field.ident.unraw() == first.unraw())
+        else {
+            return Err(syn::Error::new(
+                first.span(),
+                format!("index key `{path}` does not exist, `{}` is not a serialized field of the struct", first.unraw()),
+            ));
+        };
+        let name = &field.name;
+        if self.segments.len() == 1 {
+            return Ok(quote!(#name));
+        }
+        let mut document = quote!(__doc);
+        // 使用键的位置报告错误
+        let mut previous = field.ident.clone();
+        previous.set_span(first.span());
+        let nested = self.segments[1..].iter().map(|segment| {
+            document = quote_spanned! {previous.span()=>
+                axum_mongodb::__private::document(&#document.#previous)
+            };
+            previous = segment.clone();
+            let field = segment.unraw().to_string();
+            let message = format!(
+                "index key `{path}` does not exist, the nested document has no field `{field}`"
+            );
+            quote_spanned! {segment.span()=>
+                const {
+                    match axum_mongodb::__private::field_name(|__doc: &Self| #document, #field) {
+                        Some(name) => name,
+                        None => ::std::panic!(#message),
+                    }
+                }
+            }
+        });
+        let nested = nested.collect::<Vec<_>>();
+        Ok(quote!({ [#name, #(#nested),*].join(".") }))
+    }
+}
+
+impl From<syn::Ident> for KeyPath {
+    fn from(field: syn::Ident) -> Self {
+        Self {
+            span: field.span(),
+            segments: vec![field],
+        }
+    }
+}
+
+impl std::fmt::Display for KeyPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let segments = self
+            .segments
+            .iter()
+            .map(|segment| segment.unraw().to_string())
+            .collect::<Vec<_>>();
+        f.write_str(&segments.join("."))
+    }
+}
+
+// 字符串中的字段名，关键字转换为原始标识符，例如type对应r#type
+fn field_ident(field: &str, span: proc_macro2::Span) -> Option<syn::Ident> {
+    if syn::parse_str::<syn::Ident>(field).is_ok() {
+        return Some(syn::Ident::new(field, span));
+    }
+    let is_keyword = syn::parse_str::<syn::Ident>(&format!("r#{field}")).is_ok();
+    if is_keyword && !["self", "super", "crate", "Self"].contains(&field) {
+        return Some(syn::Ident::new_raw(field, span));
+    }
+    None
+}
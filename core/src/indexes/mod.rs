@@ -1,19 +1,43 @@
 mod compound;
+mod geo;
+mod key_path;
 mod multikey;
+mod options;
+pub use options::Collation;
 mod single;
+mod struct_index;
+mod text;
+mod ttl;
+mod wildcard;
+use crate::column::{FieldMeta, Serializer};
 use compound::CompoundIndex;
+use geo::GeoIndex;
 use multikey::MultikeyIndex;
 use single::SingleIndex;
+use struct_index::StructIndex;
 use syn::Result;
+use text::TextIndex;
+use ttl::TtlIndex;
+use wildcard::WildcardIndex;
 // 定义索引类型
 pub enum Indexes {
     Single(SingleIndex),
     Compound(CompoundIndex),
     Multikey(MultikeyIndex),
+    Text(TextIndex),
+    Geo(GeoIndex),
+    Ttl(TtlIndex),
+    Struct(StructIndex),
+    Wildcard(WildcardIndex),
 }
 
 impl Indexes {
-    pub fn parse_from_attr(attr: &syn::Attribute, field_name: &str) -> Result<Option<Self>> {
+    pub fn parse_from_attr(
+        attr: &syn::Attribute,
+        field_name: &str,
+        field_ty: &syn::Type,
+        serializer: &Serializer,
+    ) -> Result<Option<Self>> {
         if attr.path().is_ident("singleIndex") {
             Ok(Some(Self::Single(
                 SingleIndex::builder()
@@ -35,15 +59,91 @@ impl Indexes {
                     .parse_attr(attr)?
                     .build(),
             )))
+        } else if attr.path().is_ident("textIndex") {
+            Ok(Some(Self::Text(
+                TextIndex::builder()
+                    .set_field_name(field_name)
+                    .parse_attr(attr)?
+                    .build(),
+            )))
+        } else if attr.path().is_ident("geoIndex") {
+            Ok(Some(Self::Geo(
+                GeoIndex::builder()
+                    .set_field_name(field_name)
+                    .parse_attr(attr)?
+                    .build(),
+            )))
+        } else if attr.path().is_ident("ttlIndex") {
+            Ok(Some(Self::Ttl(
+                TtlIndex::builder()
+                    .set_field_name(field_name)
+                    .check_field_type(field_ty, serializer)?
+                    .parse_attr(attr)?
+                    .build(),
+            )))
+        } else if attr.path().is_ident("wildcardIndex") {
+            Ok(Some(Self::Wildcard(
+                WildcardIndex::builder()
+                    .set_field_name(field_name)
+                    .parse_attr(attr)?
+                    .build(),
+            )))
+        } else {
+            Ok(None)
+        }
+    }
+    // 解析结构体上的#[index(...)]与#[wildcardIndex(...)]
+    pub fn parse_from_struct_attr(attr: &syn::Attribute) -> Result<Option<Self>> {
+        if attr.path().is_ident("index") {
+            Ok(Some(Self::Struct(
+                StructIndex::builder().parse_attr(attr)?.build(),
+            )))
+        } else if attr.path().is_ident("wildcardIndex") {
+            Ok(Some(Self::Wildcard(
+                WildcardIndex::builder().parse_attr(attr)?.build(),
+            )))
         } else {
             Ok(None)
         }
     }
-    pub fn create_index(&self) -> proc_macro2::TokenStream {
-        match self {
-            Self::Single(single) => single.create_index(),
-            Self::Compound(compound) => compound.create_index(),
-            Self::Multikey(multikey) => multikey.create_index(),
+    // 为未指定排序规则的索引设置结构体上的默认排序规则，跳过不支持排序规则的索引
+    pub fn set_default_collation(&mut self, collation: &Collation) {
+        let options = match self {
+            Self::Single(index) => &mut index.options,
+            Self::Compound(index) => &mut index.options,
+            Self::Multikey(index) => &mut index.options,
+            Self::Ttl(index) => &mut index.options,
+            Self::Wildcard(index) => &mut index.options,
+            Self::Geo(index) if index.supports_collation() => &mut index.options,
+            Self::Struct(index) if index.supports_collation() => &mut index.options,
+            _ => return,
+        };
+        if options.collation.is_none() {
+            options.collation = Some(collation.clone());
+        }
+    }
+    // 生成结构体上全部索引，多个字段上的文本索引合并为一个，索引键根据fields转换为文档中的字段名
+    pub fn create_indexes(
+        list: &[Self],
+        fields: &[FieldMeta],
+    ) -> Result<Vec<proc_macro2::TokenStream>> {
+        let mut res = Vec::new();
+        let mut text_list = Vec::new();
+        for indexes in list {
+            match indexes {
+                Self::Single(single) => res.push(single.create_index()),
+                Self::Compound(compound) => res.push(compound.create_index(fields)?),
+                Self::Multikey(multikey) => res.push(multikey.create_index()),
+                Self::Text(text) => text_list.push(text),
+                Self::Geo(geo) => res.push(geo.create_index(fields)?),
+                Self::Ttl(ttl) => res.push(ttl.create_index()),
+                Self::Struct(index) => res.push(index.create_index(fields)?),
+                Self::Wildcard(wildcard) => res.push(wildcard.create_index(fields)?),
+            }
+        }
+        if !text_list.is_empty() {
+            res.push(TextIndex::create_index(&text_list)?);
         }
+        Ok(res)
     }
 }
@@ -1,3 +1,4 @@
+use super::options::{IndexOptions, IndexOrder};
 use quote::quote;
 use syn::{LitStr, Result, Token};
 
@@ -6,6 +7,8 @@ pub struct MultikeyIndex {
     pub name: Option<String>,
     pub unique: bool,
     pub field_name: String,
+    pub order: IndexOrder,
+    pub options: IndexOptions,
 }
 
 impl MultikeyIndex {
@@ -14,6 +17,8 @@ impl MultikeyIndex {
             name: None,
             unique: None,
             field_name: None,
+            order: None,
+            options: IndexOptions::default(),
         }
     }
 
@@ -21,24 +26,30 @@ impl MultikeyIndex {
         let field_name = &self.field_name;
         let name = &self.name;
         let unique = self.unique;
+        let order = self.order;
+        let options = self.options.create_fields();
         if self.name.is_some() {
             quote! {
                 axum_mongodb::CreateIndexOptions{
-                    keys:doc!{
-                        #field_name:1
+                    keys:axum_mongodb::__private::doc!{
+                        #field_name:#order
                     },
                     name:Some(#name.to_string()),
                     unique:#unique,
+                    #options
+                    ..Default::default()
                 }
             }
         } else {
             quote! {
                 axum_mongodb::CreateIndexOptions{
-                    keys:doc!{
-                        #field_name:1
+                    keys:axum_mongodb::__private::doc!{
+                        #field_name:#order
                     },
                     name:None,
                     unique:#unique,
+                    #options
+                    ..Default::default()
                 }
             }
         }
@@ -49,6 +60,8 @@ pub struct MultikeyIndexBuilder {
     pub name: Option<String>,
     pub unique: Option<bool>,
     pub field_name: Option<String>,
+    pub order: Option<IndexOrder>,
+    pub options: IndexOptions,
 }
 
 impl MultikeyIndexBuilder {
@@ -58,10 +71,17 @@ impl MultikeyIndexBuilder {
     }
     pub fn parse_attr(&mut self, attr: &syn::Attribute) -> Result<&mut Self> {
         attr.parse_nested_meta(|meta| {
+            if self.options.parse_meta(&meta)? {
+                return Ok(());
+            }
             if meta.path.is_ident("unique") {
                 self.unique = Some(true);
                 return Ok(());
             }
+            if meta.path.is_ident("order") {
+                self.order = Some(IndexOrder::parse_meta(&meta)?);
+                return Ok(());
+            }
             if meta.path.is_ident("name") {
                 meta.input.parse::<Token![=]>()?;
                 let value = meta.input.parse::<LitStr>()?;
@@ -74,8 +94,14 @@ impl MultikeyIndexBuilder {
                 self.field_name = Some(value.value());
                 return Ok(());
             }
-            return Ok(());
+            Err(meta.error("unsupported multikeyIndex attribute"))
         })?;
+        if self.unique == Some(true) && self.order == Some(IndexOrder::Hashed) {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "hashed index can not be unique",
+            ));
+        }
         Ok(self)
     }
     pub fn build(&self) -> MultikeyIndex {
@@ -83,6 +109,8 @@ impl MultikeyIndexBuilder {
             name: self.name.clone(),
             unique: self.unique.unwrap_or(false),
             field_name: self.field_name.clone().expect("field_name is required"),
+            order: self.order.unwrap_or_default(),
+            options: self.options.clone(),
         }
     }
 }
@@ -0,0 +1,238 @@
+use quote::quote;
+use serde_json::Value;
+use syn::{meta::ParseNestedMeta, LitBool, LitInt, LitStr, Result, Token};
+
+// 所有索引都支持的选项
+#[derive(Debug, Clone, Default)]
+pub struct IndexOptions {
+    pub sparse: bool,
+    pub partial_filter: Option<Value>,
+    pub collation: Option<Collation>,
+}
+
+impl IndexOptions {
+    // 解析通用选项，返回是否为通用选项
+    pub fn parse_meta(&mut self, meta: &ParseNestedMeta) -> Result<bool> {
+        if meta.path.is_ident("sparse") {
+            self.sparse = true;
+            return Ok(true);
+        }
+        if meta.path.is_ident("partial_filter") {
+            meta.input.parse::<Token![=]>()?;
+            let value = meta.input.parse::<LitStr>()?;
+            // 在编译期解析JSON，保证生成的过滤条件合法
+            let filter = serde_json::from_str::<Value>(&value.value()).map_err(|e| {
+                syn::Error::new_spanned(&value, format!("invalid partial_filter JSON: {e}"))
+            })?;
+            if !filter.is_object() {
+                return Err(syn::Error::new_spanned(
+                    &value,
+                    "partial_filter must be a JSON object",
+                ));
+            }
+            self.partial_filter = Some(filter);
+            return Ok(true);
+        }
+        if meta.path.is_ident("collation") {
+            self.collation = Some(Collation::parse_meta(meta)?);
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    // 合并多个字段上的选项，用于文本索引
+    pub fn merge(&mut self, other: &Self, span: &impl quote::ToTokens) -> Result<()> {
+        self.sparse |= other.sparse;
+        match (&self.partial_filter, &other.partial_filter) {
+            (Some(prev), Some(filter)) if prev != filter => Err(syn::Error::new_spanned(
+                span,
+                "partial_filter conflicts with another field of the same index",
+            )),
+            (None, Some(filter)) => {
+                self.partial_filter = Some(filter.clone());
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    // 生成CreateIndexOptions中对应的字段
+    pub fn create_fields(&self) -> proc_macro2::TokenStream {
+        let sparse = self.sparse;
+        let partial_filter = match &self.partial_filter {
+            Some(filter) => {
+                let filter = json_to_tokens(filter);
+                quote!(Some(axum_mongodb::__private::doc! #filter))
+            }
+            None => quote!(None),
+        };
+        let collation = match &self.collation {
+            Some(collation) => quote!(Some(#collation)),
+            None => quote!(None),
+        };
+        quote! {
+            sparse:#sparse,
+            partial_filter:#partial_filter,
+            collation:#collation,
+        }
+    }
+}
+
+// 索引的排序规则，例如collation(locale = "en", strength = 2)可以实现不区分大小写的唯一索引
+#[derive(Debug, Clone, PartialEq)]
+pub struct Collation {
+    pub locale: String,
+    pub strength: Option<u32>,
+    pub case_level: Option<bool>,
+    pub numeric_ordering: Option<bool>,
+}
+
+impl Collation {
+    // 解析collation(...)
+    pub fn parse_meta(meta: &ParseNestedMeta) -> Result<Self> {
+        let mut locale = None;
+        let mut strength = None;
+        let mut case_level = None;
+        let mut numeric_ordering = None;
+        meta.parse_nested_meta(|meta| {
+            if meta.path.is_ident("locale") {
+                meta.input.parse::<Token![=]>()?;
+                locale = Some(meta.input.parse::<LitStr>()?.value());
+                return Ok(());
+            }
+            if meta.path.is_ident("strength") {
+                meta.input.parse::<Token![=]>()?;
+                let value = meta.input.parse::<LitInt>()?;
+                let level = value.base10_parse::<u32>()?;
+                if !(1..=5).contains(&level) {
+                    return Err(syn::Error::new_spanned(
+                        value,
+                        "collation strength must be between 1 and 5",
+                    ));
+                }
+                strength = Some(level);
+                return Ok(());
+            }
+            if meta.path.is_ident("case_level") {
+                meta.input.parse::<Token![=]>()?;
+                case_level = Some(meta.input.parse::<LitBool>()?.value);
+                return Ok(());
+            }
+            if meta.path.is_ident("numeric_ordering") {
+                meta.input.parse::<Token![=]>()?;
+                numeric_ordering = Some(meta.input.parse::<LitBool>()?.value);
+                return Ok(());
+            }
+            Err(meta.error("unsupported collation attribute"))
+        })?;
+        let Some(locale) = locale else {
+            return Err(meta.error("collation requires locale, e.g. collation(locale = \"en\")"));
+        };
+        Ok(Self {
+            locale,
+            strength,
+            case_level,
+            numeric_ordering,
+        })
+    }
+}
+
+impl quote::ToTokens for Collation {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let locale = &self.locale;
+        let strength = match self.strength {
+            Some(strength) => quote!(Some(#strength)),
+            None => quote!(None),
+        };
+        let case_level = match self.case_level {
+            Some(case_level) => quote!(Some(#case_level)),
+            None => quote!(None),
+        };
+        let numeric_ordering = match self.numeric_ordering {
+            Some(numeric_ordering) => quote!(Some(#numeric_ordering)),
+            None => quote!(None),
+        };
+        tokens.extend(quote! {
+            axum_mongodb::IndexCollation{
+                locale:#locale.to_string(),
+                strength:#strength,
+                case_level:#case_level,
+                numeric_ordering:#numeric_ordering,
+            }
+        });
+    }
+}
+
+// 将JSON转换为axum_mongodb::__private::doc!宏的写法
+fn json_to_tokens(value: &Value) -> proc_macro2::TokenStream {
+    match value {
+        Value::Null => quote!(null),
+        Value::Bool(value) => quote!(#value),
+        Value::Number(number) => {
+            if let Some(value) = number.as_i64() {
+                match i32::try_from(value) {
+                    Ok(value) => quote!(#value),
+                    Err(_) => quote!(#value),
+                }
+            } else {
+                let value = number.as_f64().unwrap();
+                quote!(#value)
+            }
+        }
+        Value::String(value) => quote!(#value),
+        Value::Array(list) => {
+            let list = list.iter().map(json_to_tokens);
+            quote!([#(#list),*])
+        }
+        Value::Object(map) => {
+            let keys = map.keys();
+            let values = map.values().map(json_to_tokens);
+            quote!({ #(#keys: #values),* })
+        }
+    }
+}
+
+// 索引键的方向
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IndexOrder {
+    #[default]
+    Asc,
+    Desc,
+    Hashed,
+}
+
+impl IndexOrder {
+    fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "asc" => Some(Self::Asc),
+            "desc" => Some(Self::Desc),
+            "hashed" => Some(Self::Hashed),
+            _ => None,
+        }
+    }
+
+    // 解析order = "desc"
+    pub fn parse_meta(meta: &ParseNestedMeta) -> Result<Self> {
+        meta.input.parse::<Token![=]>()?;
+        let value = meta.input.parse::<LitStr>()?;
+        Self::from_str(&value.value()).ok_or_else(|| {
+            syn::Error::new_spanned(&value, "order must be \"asc\", \"desc\" or \"hashed\"")
+        })
+    }
+
+    // 解析other_fields中的name(desc)
+    pub fn parse_ident(ident: &syn::Ident) -> Result<Self> {
+        Self::from_str(&ident.to_string())
+            .ok_or_else(|| syn::Error::new_spanned(ident, "order must be asc, desc or hashed"))
+    }
+}
+
+impl quote::ToTokens for IndexOrder {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        tokens.extend(match self {
+            Self::Asc => quote!(1),
+            Self::Desc => quote!(-1),
+            Self::Hashed => quote!("hashed"),
+        })
+    }
+}
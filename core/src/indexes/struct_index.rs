@@ -0,0 +1,171 @@
+use super::{key_path::KeyPath, options::IndexOptions};
+use crate::column::FieldMeta;
+use quote::quote;
+use syn::{parse::ParseStream, LitInt, LitStr, Result, Token};
+
+// 结构体上的#[index(keys(...))]，按照声明顺序生成索引键，支持嵌套文档的路径
+#[derive(Debug)]
+pub struct StructIndex {
+    pub name: Option<String>,
+    pub unique: bool,
+    pub keys: Vec<IndexKey>,
+    pub options: IndexOptions,
+}
+
+#[derive(Debug, Clone)]
+pub struct IndexKey {
+    pub path: KeyPath,
+    pub value: IndexKeyValue,
+}
+
+#[derive(Debug, Clone)]
+pub enum IndexKeyValue {
+    Int(i32),
+    Str(String),
+}
+
+impl StructIndex {
+    pub fn supports_collation(&self) -> bool {
+        supports_collation(&self.keys)
+    }
+
+    pub fn builder() -> StructIndexBuilder {
+        StructIndexBuilder {
+            name: None,
+            unique: None,
+            keys: Vec::new(),
+            options: IndexOptions::default(),
+        }
+    }
+
+    pub fn create_index(&self, fields: &[FieldMeta]) -> Result<proc_macro2::TokenStream> {
+        let unique = self.unique;
+        let options = self.options.create_fields();
+        let keys = self
+            .keys
+            .iter()
+            .map(|key| {
+                let path = key.path.resolve(fields)?;
+                Ok(match &key.value {
+                    IndexKeyValue::Int(value) => quote!(#path:#value),
+                    IndexKeyValue::Str(value) => quote!(#path:#value),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let name = match &self.name {
+            Some(name) => quote!(Some(#name.to_string())),
+            None => quote!(None),
+        };
+        Ok(quote! {
+            axum_mongodb::CreateIndexOptions{
+                keys:axum_mongodb::__private::doc!{
+                    #(#keys),*
+                },
+                name:#name,
+                unique:#unique,
+                #options
+                ..Default::default()
+            }
+        })
+    }
+}
+
+pub struct StructIndexBuilder {
+    pub name: Option<String>,
+    pub unique: Option<bool>,
+    pub keys: Vec<IndexKey>,
+    pub options: IndexOptions,
+}
+
+impl StructIndexBuilder {
+    pub fn parse_attr(&mut self, attr: &syn::Attribute) -> Result<&mut Self> {
+        attr.parse_nested_meta(|meta| {
+            if self.options.parse_meta(&meta)? {
+                return Ok(());
+            }
+            if meta.path.is_ident("unique") {
+                self.unique = Some(true);
+                return Ok(());
+            }
+            if meta.path.is_ident("name") {
+                meta.input.parse::<Token![=]>()?;
+                let value = meta.input.parse::<LitStr>()?;
+                self.name = Some(value.value());
+                return Ok(());
+            }
+            // keys(tenant_id = 1, "address.city" = 1, created_at = -1)
+            if meta.path.is_ident("keys") {
+                let content;
+                syn::parenthesized!(content in meta.input);
+                let keys = content.parse_terminated(parse_key, Token![,])?;
+                self.keys.extend(keys);
+                return Ok(());
+            }
+            Err(meta.error("unsupported index attribute"))
+        })?;
+        if self.keys.is_empty() {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "index requires keys, e.g. #[index(keys(name = 1))]",
+            ));
+        }
+        if self.options.collation.is_some() && !supports_collation(&self.keys) {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "\"text\" and \"2d\" keys do not support collation",
+            ));
+        }
+        for (i, key) in self.keys.iter().enumerate() {
+            if self.keys[..i]
+                .iter()
+                .any(|prev| prev.path.is_same(&key.path))
+            {
+                return Err(syn::Error::new(key.path.span, "duplicate index key"));
+            }
+        }
+        Ok(self)
+    }
+    pub fn build(&self) -> StructIndex {
+        StructIndex {
+            name: self.name.clone(),
+            unique: self.unique.unwrap_or(false),
+            keys: self.keys.clone(),
+            options: self.options.clone(),
+        }
+    }
+}
+
+// 文本索引与2d索引不支持排序规则
+fn supports_collation(keys: &[IndexKey]) -> bool {
+    !keys.iter().any(
+        |key| matches!(&key.value, IndexKeyValue::Str(value) if value == "text" || value == "2d"),
+    )
+}
+
+fn parse_key(input: ParseStream) -> Result<IndexKey> {
+    let path = KeyPath::parse(input)?;
+    let span = path.span;
+    input.parse::<Token![=]>()?;
+    let value = if input.peek(LitStr) {
+        let value = input.parse::<LitStr>()?;
+        if !["hashed", "text", "2dsphere", "2d"].contains(&value.value().as_str()) {
+            return Err(syn::Error::new_spanned(
+                value,
+                "index key must be 1, -1, \"hashed\", \"text\", \"2dsphere\" or \"2d\"",
+            ));
+        }
+        IndexKeyValue::Str(value.value())
+    } else {
+        let neg = input.parse::<Option<Token![-]>>()?.is_some();
+        let value = input.parse::<LitInt>()?;
+        let value = value.base10_parse::<i32>()? * if neg { -1 } else { 1 };
+        if value != 1 && value != -1 {
+            return Err(syn::Error::new(
+                span,
+                "index key must be 1, -1, \"hashed\", \"text\", \"2dsphere\" or \"2d\"",
+            ));
+        }
+        IndexKeyValue::Int(value)
+    };
+    Ok(IndexKey { path, value })
+}
@@ -0,0 +1,178 @@
+use super::options::IndexOptions;
+use quote::quote;
+use syn::{LitInt, LitStr, Result, Token};
+
+// 文本索引，一个集合只能有一个文本索引，多个字段上的textIndex会合并为一个索引
+#[derive(Debug)]
+pub struct TextIndex {
+    pub name: Option<LitStr>,
+    pub field_name: String,
+    pub options: IndexOptions,
+    pub weight: Option<i32>,
+    pub default_language: Option<LitStr>,
+    pub language_override: Option<LitStr>,
+}
+
+impl TextIndex {
+    pub fn builder() -> TextIndexBuilder {
+        TextIndexBuilder {
+            name: None,
+            field_name: None,
+            options: IndexOptions::default(),
+            weight: None,
+            default_language: None,
+            language_override: None,
+        }
+    }
+
+    // 合并所有字段上的textIndex，生成一个索引
+    pub fn create_index(list: &[&TextIndex]) -> Result<proc_macro2::TokenStream> {
+        let name = merge_option(list, |item| item.name.as_ref(), "name")?;
+        let default_language = merge_option(
+            list,
+            |item| item.default_language.as_ref(),
+            "default_language",
+        )?;
+        let language_override = merge_option(
+            list,
+            |item| item.language_override.as_ref(),
+            "language_override",
+        )?;
+        let mut options = IndexOptions::default();
+        for item in list {
+            options.merge(&item.options, &item.field_name)?;
+        }
+        let options = options.create_fields();
+        let field_names = list.iter().map(|item| &item.field_name);
+        let weights = list.iter().filter_map(|item| {
+            let field_name = &item.field_name;
+            item.weight.map(|weight| quote!(#field_name:#weight))
+        });
+
+        let name = match name {
+            Some(name) => quote!(Some(#name.to_string())),
+            None => quote!(None),
+        };
+        let weights = if list.iter().any(|item| item.weight.is_some()) {
+            quote!(Some(axum_mongodb::__private::doc! { #(#weights),* }))
+        } else {
+            quote!(None)
+        };
+        let default_language = match default_language {
+            Some(default_language) => quote!(Some(#default_language.to_string())),
+            None => quote!(None),
+        };
+        let language_override = match language_override {
+            Some(language_override) => quote!(Some(#language_override.to_string())),
+            None => quote!(None),
+        };
+        Ok(quote! {
+            axum_mongodb::CreateIndexOptions{
+                keys:axum_mongodb::__private::doc!{
+                    #(#field_names:"text"),*
+                },
+                name:#name,
+                weights:#weights,
+                default_language:#default_language,
+                language_override:#language_override,
+                #options
+                ..Default::default()
+            }
+        })
+    }
+}
+
+// 多个字段上的同一个选项必须一致
+fn merge_option<'a>(
+    list: &[&'a TextIndex],
+    get: impl Fn(&'a TextIndex) -> Option<&'a LitStr>,
+    option: &str,
+) -> Result<Option<&'a LitStr>> {
+    let mut res: Option<&LitStr> = None;
+    for value in list.iter().filter_map(|item| get(item)) {
+        match res {
+            Some(prev) if prev.value() != value.value() => {
+                return Err(syn::Error::new_spanned(
+                    value,
+                    format!("textIndex {} conflicts with {:?}", option, prev.value()),
+                ))
+            }
+            _ => res = Some(value),
+        }
+    }
+    Ok(res)
+}
+
+pub struct TextIndexBuilder {
+    pub name: Option<LitStr>,
+    pub field_name: Option<String>,
+    pub options: IndexOptions,
+    pub weight: Option<i32>,
+    pub default_language: Option<LitStr>,
+    pub language_override: Option<LitStr>,
+}
+
+impl TextIndexBuilder {
+    pub fn set_field_name(&mut self, field_name: &str) -> &mut Self {
+        self.field_name = Some(field_name.to_string());
+        self
+    }
+    pub fn parse_attr(&mut self, attr: &syn::Attribute) -> Result<&mut Self> {
+        // 允许不带参数的#[textIndex]
+        if matches!(attr.meta, syn::Meta::Path(_)) {
+            return Ok(self);
+        }
+        attr.parse_nested_meta(|meta| {
+            if self.options.parse_meta(&meta)? {
+                return Ok(());
+            }
+            if meta.path.is_ident("name") {
+                meta.input.parse::<Token![=]>()?;
+                self.name = Some(meta.input.parse::<LitStr>()?);
+                return Ok(());
+            }
+            if meta.path.is_ident("weight") {
+                meta.input.parse::<Token![=]>()?;
+                let value = meta.input.parse::<LitInt>()?;
+                let weight = value.base10_parse::<i32>()?;
+                if !(1..=99999).contains(&weight) {
+                    return Err(syn::Error::new_spanned(
+                        value,
+                        "weight must be between 1 and 99999",
+                    ));
+                }
+                self.weight = Some(weight);
+                return Ok(());
+            }
+            if meta.path.is_ident("default_language") {
+                meta.input.parse::<Token![=]>()?;
+                self.default_language = Some(meta.input.parse::<LitStr>()?);
+                return Ok(());
+            }
+            if meta.path.is_ident("language_override") {
+                meta.input.parse::<Token![=]>()?;
+                self.language_override = Some(meta.input.parse::<LitStr>()?);
+                return Ok(());
+            }
+            Err(meta.error("unsupported textIndex attribute"))
+        })?;
+        // 文本索引只支持简单的二进制比较
+        if self.options.collation.is_some() {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "textIndex does not support collation",
+            ));
+        }
+        Ok(self)
+    }
+    pub fn build(&self) -> TextIndex {
+        TextIndex {
+            name: self.name.clone(),
+            field_name: self.field_name.clone().expect("field_name is required"),
+            options: self.options.clone(),
+            weight: self.weight,
+            default_language: self.default_language.clone(),
+            language_override: self.language_override.clone(),
+        }
+    }
+}
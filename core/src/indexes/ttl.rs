@@ -0,0 +1,273 @@
+use super::options::IndexOptions;
+use crate::column::Serializer;
+use quote::quote;
+use syn::{LitStr, Result, Token};
+
+// TTL索引，文档在日期字段的值加上expire_after之后自动删除
+#[derive(Debug)]
+pub struct TtlIndex {
+    pub name: Option<String>,
+    pub field_name: String,
+    pub options: IndexOptions,
+    pub expire_after: u64,
+}
+
+impl TtlIndex {
+    pub fn builder() -> TtlIndexBuilder {
+        TtlIndexBuilder {
+            name: None,
+            field_name: None,
+            options: IndexOptions::default(),
+            expire_after: None,
+        }
+    }
+
+    pub fn create_index(&self) -> proc_macro2::TokenStream {
+        let field_name = &self.field_name;
+        let options = self.options.create_fields();
+        let expire_after = self.expire_after;
+        let name = match &self.name {
+            Some(name) => quote!(Some(#name.to_string())),
+            None => quote!(None),
+        };
+        quote! {
+            axum_mongodb::CreateIndexOptions{
+                keys:axum_mongodb::__private::doc!{
+                    #field_name:1
+                },
+                name:#name,
+                expire_after:Some(std::time::Duration::from_secs(#expire_after)),
+                #options
+                ..Default::default()
+            }
+        }
+    }
+}
+
+pub struct TtlIndexBuilder {
+    pub name: Option<String>,
+    pub field_name: Option<String>,
+    pub options: IndexOptions,
+    pub expire_after: Option<u64>,
+}
+
+impl TtlIndexBuilder {
+    pub fn set_field_name(&mut self, field_name: &str) -> &mut Self {
+        self.field_name = Some(field_name.to_string());
+        self
+    }
+    // TTL索引只对BSON日期生效，其他日期类型默认序列化为字符串或文档，索引不会删除任何文档
+    pub fn check_field_type(
+        &mut self,
+        ty: &syn::Type,
+        serializer: &Serializer,
+    ) -> Result<&mut Self> {
+        if !is_bson_date(ty, serializer) {
+            return Err(syn::Error::new_spanned(
+                ty,
+                "ttlIndex can only be used on a field stored as a BSON date, e.g. bson::DateTime, or chrono::DateTime<Utc> with #[serde(with = \"bson::serde_helpers::chrono_datetime_as_bson_datetime\")]",
+            ));
+        }
+        Ok(self)
+    }
+    pub fn parse_attr(&mut self, attr: &syn::Attribute) -> Result<&mut Self> {
+        attr.parse_nested_meta(|meta| {
+            if self.options.parse_meta(&meta)? {
+                return Ok(());
+            }
+            if meta.path.is_ident("name") {
+                meta.input.parse::<Token![=]>()?;
+                let value = meta.input.parse::<LitStr>()?;
+                self.name = Some(value.value());
+                return Ok(());
+            }
+            if meta.path.is_ident("expire_after") {
+                meta.input.parse::<Token![=]>()?;
+                let value = meta.input.parse::<LitStr>()?;
+                self.expire_after = Some(parse_duration(&value)?);
+                return Ok(());
+            }
+            Err(meta.error("unsupported ttlIndex attribute"))
+        })?;
+        if self.expire_after.is_none() {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "ttlIndex requires expire_after, e.g. #[ttlIndex(expire_after = \"24h\")]",
+            ));
+        }
+        Ok(self)
+    }
+    pub fn build(&self) -> TtlIndex {
+        TtlIndex {
+            name: self.name.clone(),
+            field_name: self.field_name.clone().expect("field_name is required"),
+            options: self.options.clone(),
+            expire_after: self.expire_after.expect("expire_after is required"),
+        }
+    }
+}
+
+// bson::DateTime（允许Option包裹），或者通过bson::serde_helpers中的*_as_bson_datetime序列化为BSON日期的字段
+fn is_bson_date(ty: &syn::Type, serializer: &Serializer) -> bool {
+    match serializer {
+        Serializer::Default => is_bson_date_time(ty),
+        Serializer::SerializeWith(path) | Serializer::With(path) => path
+            .segments
+            .iter()
+            .any(|segment| segment.ident.to_string().ends_with("as_bson_datetime")),
+    }
+}
+
+// chrono::DateTime<Tz>带有泛型参数，bson::DateTime没有
+fn is_bson_date_time(ty: &syn::Type) -> bool {
+    let syn::Type::Path(syn::TypePath { qself: None, path }) = ty else {
+        return false;
+    };
+    let Some(segment) = path.segments.last() else {
+        return false;
+    };
+    match &segment.arguments {
+        syn::PathArguments::None => {
+            let parent = path.segments.iter().rev().nth(1);
+            segment.ident == "DateTime" && parent.is_none_or(|parent| parent.ident == "bson")
+        }
+        syn::PathArguments::AngleBracketed(args) if segment.ident == "Option" => {
+            match args.args.first() {
+                Some(syn::GenericArgument::Type(inner)) => is_bson_date_time(inner),
+                _ => false,
+            }
+        }
+        _ => false,
+    }
+}
+
+// 解析可读的时长，例如"30s"、"15m"、"24h"、"7d"、"1h30m"，纯数字表示秒
+fn parse_duration(value: &LitStr) -> Result<u64> {
+    let error = || {
+        syn::Error::new_spanned(
+            value,
+            "invalid duration, expected e.g. \"30s\", \"15m\", \"24h\", \"7d\", \"2w\" or \"1h30m\"",
+        )
+    };
+    let text = value.value();
+    let text = text.trim();
+    if text.is_empty() {
+        return Err(error());
+    }
+    if let Ok(secs) = text.parse::<u64>() {
+        return check_range(value, secs);
+    }
+    let mut total: u64 = 0;
+    let mut number = String::new();
+    for c in text.chars().filter(|c| !c.is_whitespace()) {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let unit = match c {
+            's' => 1,
+            'm' => 60,
+            'h' => 60 * 60,
+            'd' => 24 * 60 * 60,
+            'w' => 7 * 24 * 60 * 60,
+            _ => return Err(error()),
+        };
+        let n = number.parse::<u64>().map_err(|_| error())?;
+        total = n
+            .checked_mul(unit)
+            .and_then(|secs| total.checked_add(secs))
+            .ok_or_else(error)?;
+        number.clear();
+    }
+    if !number.is_empty() {
+        return Err(error());
+    }
+    check_range(value, total)
+}
+
+// expireAfterSeconds在MongoDB中是32位整数
+fn check_range(value: &LitStr, secs: u64) -> Result<u64> {
+    if secs > i32::MAX as u64 {
+        return Err(syn::Error::new_spanned(
+            value,
+            format!("expire_after must not exceed {} seconds", i32::MAX),
+        ));
+    }
+    Ok(secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(value: &str) -> Result<u64> {
+        parse_duration(&LitStr::new(value, proc_macro2::Span::call_site()))
+    }
+
+    #[test]
+    fn parse_duration_units() {
+        assert_eq!(parse("90").unwrap(), 90);
+        assert_eq!(parse("30s").unwrap(), 30);
+        assert_eq!(parse("15m").unwrap(), 15 * 60);
+        assert_eq!(parse("24h").unwrap(), 24 * 60 * 60);
+        assert_eq!(parse("7d").unwrap(), 7 * 24 * 60 * 60);
+        assert_eq!(parse("2w").unwrap(), 2 * 7 * 24 * 60 * 60);
+        assert_eq!(parse("1h30m").unwrap(), 90 * 60);
+        assert_eq!(parse(" 1d 12h ").unwrap(), 36 * 60 * 60);
+    }
+
+    #[test]
+    fn parse_duration_rejects_invalid_values() {
+        for value in ["", " ", "h", "24x", "1.5h", "-1h", "1h30"] {
+            assert!(parse(value).is_err(), "{value:?} should be rejected");
+        }
+    }
+
+    #[test]
+    fn parse_duration_rejects_values_out_of_range() {
+        assert_eq!(parse("2147483647").unwrap(), i32::MAX as u64);
+        assert!(parse("2147483648").is_err());
+        assert!(parse("99999999999h").is_err());
+        assert!(parse("99999999999999999999s").is_err());
+    }
+
+    #[test]
+    fn bson_date_fields() {
+        let ty = |ty: &str| syn::parse_str::<syn::Type>(ty).unwrap();
+        let with = |path: &str| Serializer::With(syn::parse_str(path).unwrap());
+        assert!(is_bson_date(&ty("bson::DateTime"), &Serializer::Default));
+        assert!(is_bson_date(
+            &ty("mongodb::bson::DateTime"),
+            &Serializer::Default
+        ));
+        assert!(is_bson_date(&ty("DateTime"), &Serializer::Default));
+        assert!(is_bson_date(
+            &ty("Option<bson::DateTime>"),
+            &Serializer::Default
+        ));
+        assert!(!is_bson_date(
+            &ty("chrono::DateTime<Utc>"),
+            &Serializer::Default
+        ));
+        assert!(!is_bson_date(
+            &ty("std::time::SystemTime"),
+            &Serializer::Default
+        ));
+        assert!(!is_bson_date(
+            &ty("time::OffsetDateTime"),
+            &Serializer::Default
+        ));
+        assert!(is_bson_date(
+            &ty("chrono::DateTime<Utc>"),
+            &with("bson::serde_helpers::chrono_datetime_as_bson_datetime")
+        ));
+        assert!(is_bson_date(
+            &ty("time::OffsetDateTime"),
+            &with("bson::serde_helpers::time_0_3_offsetdatetime_as_bson_datetime")
+        ));
+        assert!(!is_bson_date(
+            &ty("bson::DateTime"),
+            &with("bson::serde_helpers::bson_datetime_as_rfc3339_string")
+        ));
+    }
+}
@@ -0,0 +1,143 @@
+use super::{key_path::KeyPath, options::IndexOptions};
+use crate::column::FieldMeta;
+use quote::quote;
+use syn::{LitStr, Result, Token};
+
+// 通配符索引，字段上生成`field.$**`，结构体上生成`$**`并支持wildcard_projection
+#[derive(Debug)]
+pub struct WildcardIndex {
+    pub name: Option<String>,
+    pub field_name: Option<String>,
+    pub options: IndexOptions,
+    pub include: Vec<KeyPath>,
+    pub exclude: Vec<KeyPath>,
+}
+
+impl WildcardIndex {
+    pub fn builder() -> WildcardIndexBuilder {
+        WildcardIndexBuilder {
+            name: None,
+            field_name: None,
+            options: IndexOptions::default(),
+            include: Vec::new(),
+            exclude: Vec::new(),
+        }
+    }
+
+    // include与exclude中的路径与#[index]的键一样根据fields转换为文档中的字段名
+    pub fn create_index(&self, fields: &[FieldMeta]) -> Result<proc_macro2::TokenStream> {
+        let key = match &self.field_name {
+            Some(field_name) => format!("{field_name}.$**"),
+            None => "$**".to_string(),
+        };
+        let options = self.options.create_fields();
+        let name = match &self.name {
+            Some(name) => quote!(Some(#name.to_string())),
+            None => quote!(None),
+        };
+        let include = resolve_paths(&self.include, fields)?;
+        let exclude = resolve_paths(&self.exclude, fields)?;
+        let wildcard_projection = if !include.is_empty() {
+            quote!(Some(axum_mongodb::__private::doc! { #(#include:1),* }))
+        } else if !exclude.is_empty() {
+            quote!(Some(axum_mongodb::__private::doc! { #(#exclude:0),* }))
+        } else {
+            quote!(None)
+        };
+        Ok(quote! {
+            axum_mongodb::CreateIndexOptions{
+                keys:axum_mongodb::__private::doc!{
+                    #key:1
+                },
+                name:#name,
+                wildcard_projection:#wildcard_projection,
+                #options
+                ..Default::default()
+            }
+        })
+    }
+}
+
+pub struct WildcardIndexBuilder {
+    pub name: Option<String>,
+    pub field_name: Option<String>,
+    pub options: IndexOptions,
+    pub include: Vec<KeyPath>,
+    pub exclude: Vec<KeyPath>,
+}
+
+impl WildcardIndexBuilder {
+    pub fn set_field_name(&mut self, field_name: &str) -> &mut Self {
+        self.field_name = Some(field_name.to_string());
+        self
+    }
+    pub fn parse_attr(&mut self, attr: &syn::Attribute) -> Result<&mut Self> {
+        // 允许不带参数的#[wildcardIndex]
+        if matches!(attr.meta, syn::Meta::Path(_)) {
+            return self.check(attr);
+        }
+        attr.parse_nested_meta(|meta| {
+            if self.options.parse_meta(&meta)? {
+                return Ok(());
+            }
+            if meta.path.is_ident("name") {
+                meta.input.parse::<Token![=]>()?;
+                let value = meta.input.parse::<LitStr>()?;
+                self.name = Some(value.value());
+                return Ok(());
+            }
+            // include(a, "b.c")与exclude(...)只能用于结构体上的通配符索引
+            if meta.path.is_ident("include") || meta.path.is_ident("exclude") {
+                if self.field_name.is_some() {
+                    return Err(meta.error(
+                        "include and exclude are only supported by struct-level wildcardIndex",
+                    ));
+                }
+                let content;
+                syn::parenthesized!(content in meta.input);
+                let paths = content.parse_terminated(KeyPath::parse, Token![,])?;
+                if meta.path.is_ident("include") {
+                    self.include.extend(paths);
+                } else {
+                    self.exclude.extend(paths);
+                }
+                return Ok(());
+            }
+            Err(meta.error("unsupported wildcardIndex attribute"))
+        })?;
+        self.check(attr)
+    }
+    fn check(&mut self, attr: &syn::Attribute) -> Result<&mut Self> {
+        if !self.include.is_empty() && !self.exclude.is_empty() {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "wildcardIndex cannot use include and exclude together",
+            ));
+        }
+        if self.options.sparse {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "wildcardIndex does not support sparse",
+            ));
+        }
+        Ok(self)
+    }
+    pub fn build(&self) -> WildcardIndex {
+        WildcardIndex {
+            name: self.name.clone(),
+            field_name: self.field_name.clone(),
+            options: self.options.clone(),
+            include: self.include.clone(),
+            exclude: self.exclude.clone(),
+        }
+    }
+}
+
+fn resolve_paths(paths: &[KeyPath], fields: &[FieldMeta]) -> Result<Vec<proc_macro2::TokenStream>> {
+    for (i, path) in paths.iter().enumerate() {
+        if paths[..i].iter().any(|prev| prev.is_same(path)) {
+            return Err(syn::Error::new(path.span, "duplicate wildcardIndex path"));
+        }
+    }
+    paths.iter().map(|path| path.resolve(fields)).collect()
+}
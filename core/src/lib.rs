@@ -5,8 +5,14 @@
 
 - [`Column`]：`#[derive(Column)]`Derive宏，用于收集结构体元信息
 
+- [`Fields`]：`#[derive(Fields)]`Derive宏，为嵌套文档生成字段名常量以及字段枚举
+
+- [`Projection`]：`#[derive(Projection)]`Derive宏，定义模型的只读子集，通过`Server<T>::find_as`查询
+
 - [`macro@main`]：`#[axum_mongodb::main]`属性宏，在main函数上使用，主要生成相关结构体，例如Servers、Server
 
+- [`export_models!`]：在库crate中导出模型列表，在[`macro@main`]上通过`include(...)`引入
+
 - [`macro@inject`]：`#[axum_mongodb::inject]`属性宏，用于axum handler上，主要作用是替换`DBServers`到`axum_mongodb::MongoDbServer<crate::Servers>`
 
 该库不支持直接使用，具体用法请查看[axum_mongodb](https://docs.rs/axum-mongodb/latest/axum_mongodb/)
@@ -16,9 +22,10 @@
 #[doc(hidden)]
 use proc_macro::TokenStream;
 mod column;
-pub(crate) mod column_meta;
-use column::collect_meta;
-use column_meta::COLUMN_META;
+use column::{
+    collect_fields, collect_meta, projection, ColumnOptions, ExportModels, IncludeModels,
+    MainOptions, SyncMode,
+};
 
 #[doc(hidden)]
 use quote::quote;
@@ -26,15 +33,38 @@ use quote::quote;
 #[doc(hidden)]
 use syn::parse_macro_input;
 pub(crate) mod indexes;
+pub(crate) mod naming;
 
 /**
 Column Derive宏，用于收集结构体元信息，以及初始化mongodb的索引
 
-每次修改索引时，默认不删除全部索引，但会尝试删除老的索引
+初始化时会将集合上的索引与声明的索引同步：与声明一致的索引保持不变，keys相同但选项发生变化的索引删除后重新创建，
+未创建的索引会被创建，同步结果通过`axum_mongodb::IndexSyncReport`返回
 
 属性列表
 
-- dropIndexes：是否删除当前集合的全部索引，默认不删除
+- column：结构体属性，`#[column(collection = "...")]`用于指定集合名，默认为结构体名小写后加s（例如`Todo`对应`todos`）；
+  `Servers`中的字段名默认与集合名一致，指定了collection或naming且结果与[`macro@main`]上的命名策略不同时会在编译期报错，
+  需要在`models(...)`中指定字段名，例如`models(app_users = User)`、`models(user_profiles = UserProfile)`；
+  `#[column(naming = "...")]`用于指定该模型的命名策略，覆盖[`macro@main`]上的默认策略，可选值：
+    - `lowercase_s`：小写后加s，默认值，`OrderItem`对应`orderitems`
+    - `snake_case`：`OrderItem`对应`order_item`
+    - `camelCase`：`OrderItem`对应`orderItem`
+    - `plural_snake_case`：蛇形命名并转为英文复数，`OrderItem`对应`order_items`，`Company`对应`companies`，`Person`对应`people`
+    - `as_is`：与结构体名一致
+
+  `#[column(instances(OrderEvent = Event<OrderPlaced>, ...))]`用于泛型模型，每个实例生成一个同名的类型别名，对应单独的集合，
+  集合名以及`Servers`中的字段名由实例名决定，在`models(...)`中通过类型别名注册，例如`models(OrderEvent)`；
+  未指定instances的泛型模型的所有实例共用同一个集合
+
+  `#[column(collation(...))]`用于指定该模型所有索引默认的排序规则，索引上的`collation`会覆盖默认值，文本索引与`2d`索引不使用默认值
+
+  `#[column(patch)]`用于生成`{结构体名}Patch`，见下方的部分更新；
+  `#[column(no_builders)]`用于不生成`{结构体名}Filter`、`{结构体名}Update`以及`filter()`、`update()`方法，
+  模型自己定义了同名的方法或类型时使用，不能与`patch`同时使用；
+  用于字段时`#[column(immutable)]`表示该字段创建后不能修改，不会生成`set_`等更新方法，也不会出现在`{结构体名}Patch`中
+
+- dropIndexes：是否删除当前集合中未声明的索引（`_id_`除外），默认不删除
 
 - singleIndex：[单索引](https://www.mongodb.com/docs/manual/core/indexes/index-types/index-single/)
 
@@ -42,24 +72,128 @@ Column Derive宏，用于收集结构体元信息，以及初始化mongodb的索
 
 - multikeyIndex：[多键索引](https://www.mongodb.com/docs/manual/core/indexes/index-types/index-multikey/)
 
+- textIndex：[文本索引](https://www.mongodb.com/docs/manual/core/indexes/index-types/index-text/)，
+  一个集合只能有一个文本索引，多个字段上的textIndex会合并为一个索引，支持`weight`、`default_language`、`language_override`以及`name`
+
+- geoIndex：[地理空间索引](https://www.mongodb.com/docs/manual/core/indexes/index-types/index-geospatial/)，
+  `kind`可选`2dsphere`（默认）与`2d`，`2d`索引支持`min`、`max`、`bits`，
+  通过`other_fields(...)`可以与其他字段组成复合索引，嵌套文档的字段使用点号分隔的字符串
+
+- ttlIndex：[TTL索引](https://www.mongodb.com/docs/manual/core/index-ttl/)，只能用于存储为BSON日期的字段，即`bson::DateTime`，
+  或者通过`#[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]`等序列化为BSON日期的`chrono`、`time`类型，
+  默认序列化为字符串的日期类型不会过期；`expire_after`为可读的时长，例如`"30s"`、`"15m"`、`"24h"`、`"7d"`、`"1h30m"`，最大为`i32::MAX`秒
+
+- wildcardIndex：[通配符索引](https://www.mongodb.com/docs/manual/core/indexes/index-types/index-wildcard/)，
+  用于字段时生成`{ "field.$**": 1 }`；用于结构体时生成`{ "$**": 1 }`，
+  并且可以通过`include(...)`或`exclude(...)`指定`wildcardProjection`，例如`#[wildcardIndex(exclude(secret, "metadata.raw"))]`，两者不能同时使用，
+  路径与`index`的键相同，使用Rust字段名并转换为serde重命名后的字段名，字段不存在时编译失败
+
+- index：结构体属性，按照声明顺序定义索引键，可以重复使用，值可以是`1`、`-1`、`"hashed"`、`"text"`、`"2dsphere"`、`"2d"`，
+  嵌套文档的字段使用点号分隔的字符串，例如`#[index(keys(tenant_id = 1, "address.city" = 1, created_at = -1), unique, name = "...")]`，
+  键使用Rust字段名，生成索引时转换为serde重命名后的字段名，例如`#[serde(rename = "_id")] id`对应`id = 1`；
+  编译期会沿着字段类型检查每个键都存在，嵌套文档的结构体需要使用[`Fields`]，经过`Option`与`Vec`时使用元素类型中的字段
+
+字段名
+
+同时会在`{结构体名}Fields`中为每个字段生成文档中字段名的常量，以及`{结构体名}Field`枚举，字段名遵循serde的`rename`与`rename_all`，
+使用`skip`、`skip_serializing`、`flatten`的字段不会生成，例如`#[serde(rename = "_id")] id`对应`TodoFields::ID == "_id"`与`TodoField::Id`；
+索引同样建立在重命名后的字段名上。枚举实现了`axum_mongodb::Field`，嵌套文档的字段可以通过`join`拼接，
+例如`UserField::Address.join(AddressField::City)`得到`"address.city"`，嵌套文档的结构体需要使用[`Fields`]
+
+查询条件
+
+同时会生成`{结构体名}Filter`，通过`Todo::filter().completed().eq(true)`构建`axum_mongodb::Filter<Todo>`，
+比较的值必须能通过`axum_mongodb::FieldValue`转换为字段的类型，并按照字段的序列化方式（包括serde的`serialize_with`与`with`）转换为BSON，
+`Vec`字段的`contains`与`contains_all`同样使用该字段的序列化方式，无法序列化时`into_document()`返回`axum_mongodb::ValueError`，
+条件之间通过`and`、`or`以及`!`组合，字段方法的可见性与字段一致
+
+更新操作
+
+同时会生成`{结构体名}Update`，通过`Todo::update().set_description(..).inc_views(1)`构建`axum_mongodb::Update<Todo>`，
+每个字段生成`set_`方法，`Option`字段额外生成`unset_`，数值字段生成`inc_`，`Vec`字段生成`push_`，
+`_id`以及`#[column(immutable)]`的字段不能修改因此不生成方法；同一个字段只保留最后一次操作，
+连续的`inc_`累加增量，连续的`push_`合并到同一个`$each`中，值无法序列化时`into_document()`返回`axum_mongodb::ValueError`
+
+部分更新
+
+使用`#[column(patch)]`时会生成`{结构体名}Patch`，除`_id`与`#[column(immutable)]`外的每个字段都是`Option`并实现了`Deserialize`，
+用于PATCH接口的请求体，`into_update()`只为存在的字段生成`$set`，`Option`字段为`null`时设置为`null`
+
+`_id`
+
+序列化为`_id`的字段（例如`#[serde(rename = "_id")] id: ObjectId`）决定`axum_mongodb::Repository`中`find_by_id`等方法的id类型，
+id按照该字段的序列化方式转换为BSON，没有该字段时为`ObjectId`
+
+singleIndex、compoundIndex、multikeyIndex支持通过`order`指定方向，可选`asc`（默认）、`desc`、`hashed`，
+compoundIndex的`other_fields`中可以为每个字段单独指定方向，例如`other_fields(name(desc), age)`，
+嵌套文档的字段使用点号分隔的字符串，例如`other_fields("address.city"(desc))`
+
+字段上的索引建立在文档中的字段名上，遵循serde的`rename`与`rename_all`，
+例如`#[serde(rename_all = "camelCase")]`时`created_at`上的`singleIndex`对应`{ "createdAt": 1 }`，
+`other_fields`同样使用Rust字段名并转换为文档中的字段名，字段不存在时编译失败
+
+所有索引属性都支持以下选项
+
+- sparse：[稀疏索引](https://www.mongodb.com/docs/manual/core/index-sparse/)
+
+- partial_filter：[部分索引](https://www.mongodb.com/docs/manual/core/index-partial/)的过滤条件，JSON格式，在编译期解析，
+  例如`partial_filter = r#"{ "deleted": false }"#`
+
+- collation：[排序规则](https://www.mongodb.com/docs/manual/reference/collation/)，支持`locale`（必填）、`strength`（1～5）、`case_level`、`numeric_ordering`，
+  例如`collation(locale = "en", strength = 2)`可以实现不区分大小写的唯一索引，文本索引与`2d`索引不支持排序规则；
+  排序规则是索引标识的一部分，keys相同但排序规则不同的索引视为不同的索引
+
 
 # Example
-```rust
+```rust,ignore
 #[derive(Debug, Clone, Column)]
+#[column(collection = "app_users")]
 #[dropIndexes]
+#[index(keys(tenant_id = 1, "address.city" = 1, created_at = -1), unique)]
+#[wildcardIndex(include(name, "address.city"))]
 struct User {
-    #[singleIndex(unique)]
+    #[singleIndex(unique, partial_filter = r#"{ "deleted": false }"#)]
     name: String,
-    #[compoundIndex(unique, other_fields(name))]
+    #[singleIndex(unique, collation(locale = "en", strength = 2))]
+    username: String,
+    #[compoundIndex(unique, order = "desc", other_fields(name(desc), address))]
     age: i32,
     #[multikeyIndex(unique, field_name = "age")]
-    address: String,
+    address: Address,
+    #[textIndex(weight = 10, default_language = "english", language_override = "lang")]
+    title: String,
+    #[textIndex(weight = 2)]
+    description: String,
+    #[geoIndex(kind = "2dsphere", other_fields(name))]
+    location: Document,
+    #[geoIndex(kind = "2d", min = -180.0, max = 180.0, bits = 26)]
+    legacy_location: Vec<f64>,
+    #[ttlIndex(expire_after = "24h")]
+    expire_at: bson::DateTime,
+    #[singleIndex(sparse)]
+    deleted: Option<bool>,
+    #[wildcardIndex]
+    metadata: Document,
+    #[singleIndex(order = "hashed")]
+    tenant_id: String,
+    created_at: bson::DateTime,
 }
 ```
  */
 #[proc_macro_derive(
     Column,
-    attributes(dropIndexes, singleIndex, compoundIndex, multikeyIndex)
+    attributes(
+        column,
+        index,
+        dropIndexes,
+        singleIndex,
+        compoundIndex,
+        multikeyIndex,
+        textIndex,
+        geoIndex,
+        ttlIndex,
+        wildcardIndex
+    )
 )]
 pub fn column_derive(input: TokenStream) -> TokenStream {
     let st = syn::parse_macro_input!(input as syn::DeriveInput);
@@ -71,82 +205,261 @@ pub fn column_derive(input: TokenStream) -> TokenStream {
     if drop_indexes.is_some() {
         drop = true;
     }
-    collect_meta(&st, drop)
+    ColumnOptions::parse_attrs(&st.attrs)
+        .and_then(|options| collect_meta(&st, drop, &options))
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/**
+Fields Derive宏，为嵌套文档等非集合结构体生成`{结构体名}Fields`中的字段名常量以及`{结构体名}Field`枚举，规则与[`Column`]一致
+
+# Example
+```rust,ignore
+#[derive(Debug, Clone, Serialize, Deserialize, Fields)]
+#[serde(rename_all = "camelCase")]
+struct Address {
+    city: String,
+    zip_code: String,
+}
+
+assert_eq!(AddressFields::ZIP_CODE, "zipCode");
+assert_eq!(UserField::Address.join(AddressField::City), "address.city");
+```
+ */
+#[proc_macro_derive(Fields)]
+pub fn fields_derive(input: TokenStream) -> TokenStream {
+    let st = syn::parse_macro_input!(input as syn::DeriveInput);
+    collect_fields(&st)
         .unwrap_or_else(|e| e.to_compile_error())
         .into()
 }
 
 /**
-最主要的宏，为Server<T>实现[`axum::extract::FromRequestParts`]等
+Projection Derive宏，定义模型的只读子集，只查询需要的字段
+
+通过`#[projection(of = Todo)]`指定模型，编译期会检查每个字段在模型中存在、类型与模型中的字段一致（也可以是对应的`Option`），
+并且序列化后的字段名与模型一致（例如模型中`#[serde(rename = "_id")] id`，投影中也需要相同的rename），
+访问模型的字段需要相应的可见性。
+
+通过`#[axum_mongodb::main]`生成的`Server<T>::find_as::<P>(filter)`与`find_one_as`查询，会自动设置投影并反序列化为投影类型，
+投影中没有`_id`时会排除`_id`
+
 # Example
-```rust
+```rust,ignore
+#[derive(Debug, Deserialize, Serialize, Projection)]
+#[projection(of = Todo)]
+pub struct TodoSummary {
+    #[serde(rename = "_id")]
+    id: ObjectId,
+    description: String,
+}
+
+let summaries: Vec<TodoSummary> = todo
+    .find_as::<TodoSummary>(Todo::filter().completed().eq(false).into_document()?)
+    .await?
+    .try_collect()
+    .await?;
+```
+ */
+#[proc_macro_derive(Projection, attributes(projection))]
+pub fn projection_derive(input: TokenStream) -> TokenStream {
+    let st = syn::parse_macro_input!(input as syn::DeriveInput);
+    projection(&st)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/**
+最主要的宏，为Server<T>实现[`axum::extract::FromRequestParts`]、`axum_mongodb::Repository`等
+
+属性列表
+
+- models：需要注册的模型列表，例如`models(Todo, user::User, domain::Order)`，模型可以定义在子模块或者其他crate中，每个模型对应`Servers`中的一个字段，
+  字段名默认为对类型名应用naming后的结果，得到关键字时使用原始标识符（例如`Type`对应`servers.r#type`），也可以通过`models(todo_list = Todo)`指定
+
+  字段名不会读取模型上的`#[column(collection = "...")]`，宏展开时只能看到类型路径；模型通过`collection`或`naming`使用了其他集合名时，
+  必须显式指定字段名，例如`#[column(collection = "app_users")]`的`User`需要写成`models(app_users = User)`，未指定时编译失败
+
+- include：引入其他crate通过[`export_models!`]导出的模型，例如`include(domain::domain_models)`
+
+- naming：全局默认的集合命名策略，可选值同[`Column`]的`#[column(naming = "...")]`，默认为`lowercase_s`
+
+- sync_mode：初始化时索引的同步模式，可选值：
+    - `apply`：将集合上的索引与声明的索引同步，默认值
+    - `plan`：只通过tracing输出同步计划，不修改索引，需要开启`axum-mongodb`的`tracing`特性，
+      未开启时可以通过`MongoDbServer::<Servers>::plan_indexes(db)`获取同步计划
+    - `off`：不做任何处理
+
+# Example
+```rust,ignore
 #[tokio::main]
-#[axum_mongodb::main]
+#[axum_mongodb::main(models(Todo, User), naming = "plural_snake_case")]
 async fn main() {
     //...
 }
 ```
 */
 #[proc_macro_attribute]
-pub fn main(_attr: TokenStream, input: TokenStream) -> TokenStream {
+pub fn main(attr: TokenStream, input: TokenStream) -> TokenStream {
+    let mut options = MainOptions::default();
+    let parser = syn::meta::parser(|meta| options.parse_meta(meta));
+    parse_macro_input!(attr with parser);
     let st = parse_macro_input!(input as syn::ItemFn);
+    let naming = options.naming.to_tokens();
+    let sync_mode = options.sync_mode.to_tokens();
+    let servers = column::servers(&options);
     let mut res = proc_macro2::TokenStream::new();
+    if options.sync_mode == SyncMode::Plan {
+        res.extend(quote!(
+            axum_mongodb::__private::require_tracing!(
+                "sync_mode = \"plan\" outputs the index plan through tracing, enable the `tracing` feature of axum-mongodb or use `MongoDbServer::<Servers>::plan_indexes(db)` instead"
+            );
+        ));
+    }
     res.extend(quote!(
         #[derive(Debug, Clone)]
-        pub struct Server<T>(mongodb::Collection<T>);
+        pub struct Server<T>(axum_mongodb::__private::mongodb::Collection<T>);
 
         unsafe impl<T> Send for Server<T> {}
         unsafe impl<T> Sync for Server<T> {}
 
         impl<T> std::ops::Deref for Server<T> {
-            type Target = mongodb::Collection<T>;
+            type Target = axum_mongodb::__private::mongodb::Collection<T>;
 
             fn deref(&self) -> &Self::Target {
                 &self.0
             }
         }
 
-        impl<T> AsRef<mongodb::Collection<T>> for Server<T> {
-            fn as_ref(&self) -> &mongodb::Collection<T> {
+        impl<T> AsRef<axum_mongodb::__private::mongodb::Collection<T>> for Server<T> {
+            fn as_ref(&self) -> &axum_mongodb::__private::mongodb::Collection<T> {
                 &self.0
             }
         }
 
-        #[axum_mongodb::async_trait]
+        impl<T> axum_mongodb::Repository<T> for Server<T>
+        where
+            T: axum_mongodb::ModelId
+                + axum_mongodb::__private::serde::Serialize
+                + axum_mongodb::__private::serde::de::DeserializeOwned
+                + Unpin
+                + Send
+                + Sync
+                + 'static,
+        {
+            fn collection(&self) -> &axum_mongodb::__private::mongodb::Collection<T> {
+                &self.0
+            }
+        }
+
+        impl<T> Server<T> {
+            /// 使用投影类型`P`查询，只返回`P`中的字段
+            pub async fn find_as<P>(
+                &self,
+                filter: impl Into<Option<axum_mongodb::__private::mongodb::bson::Document>>,
+            ) -> axum_mongodb::__private::mongodb::error::Result<axum_mongodb::__private::mongodb::Cursor<P>>
+            where
+                P: axum_mongodb::Projection<Model = T>,
+            {
+                axum_mongodb::find_as(&self.0, filter).await
+            }
+
+            /// 使用投影类型`P`查询一个文档，只返回`P`中的字段
+            pub async fn find_one_as<P>(
+                &self,
+                filter: impl Into<Option<axum_mongodb::__private::mongodb::bson::Document>>,
+            ) -> axum_mongodb::__private::mongodb::error::Result<Option<P>>
+            where
+                P: axum_mongodb::Projection<Model = T>,
+            {
+                axum_mongodb::find_one_as(&self.0, filter).await
+            }
+        }
+
+        // 在初始化时同步模型上声明的索引
+        #[axum_mongodb::__private::async_trait]
+        impl<T> axum_mongodb::CollectionInit for Server<T>
+        where
+            T: axum_mongodb::Column + Send + Sync,
+        {
+            async fn init(&self) -> ::std::result::Result<axum_mongodb::IndexSyncReport, axum_mongodb::InitError> {
+                axum_mongodb::sync_indexes(&self.0, &T::indexes(), T::drop_undeclared()).await
+            }
+
+            async fn plan(&self) -> ::std::result::Result<axum_mongodb::IndexPlan, axum_mongodb::InitError> {
+                axum_mongodb::plan_indexes(&self.0, &T::indexes(), T::drop_undeclared()).await
+            }
+        }
+
+        #[axum_mongodb::__private::async_trait]
         impl<T> axum_mongodb::NewWithDb for Server<T>
         where
-            Self: axum_mongodb::CollectionInit,
+            T: axum_mongodb::Column + Send + Sync,
         {
-            async fn new(db: mongodb::Database) -> Self {
-                //通过类型名称设置集合
-                let type_name = std::any::type_name::<T>();
-                let mut collection_name = type_name.split("::").last().unwrap().to_lowercase();
-                collection_name.push('s');
-                let collection = db.collection::<T>(&collection_name);
+            async fn new(db: axum_mongodb::__private::mongodb::Database) -> ::std::result::Result<Self, axum_mongodb::InitError> {
+                //通过Column宏生成的集合名设置集合
+                let collection = db.collection::<T>(T::collection_name(#naming));
                 let res = Self(collection);
-                res.init().await;
-                res
+                axum_mongodb::init_collection(&res, #sync_mode).await?;
+                Ok(res)
             }
         }
 
-        #[axum_mongodb::inject_meta]
-        pub struct Servers {}
+        #servers
 
-        #[axum_mongodb::async_trait]
-        impl<S> axum::extract::FromRequestParts<S> for crate::Servers
+        #[axum_mongodb::__private::async_trait]
+        impl<S, T> axum_mongodb::__private::axum::extract::FromRequestParts<S> for Server<T>
         where
             S: Send + Sync,
+            T: Send + Sync,
+            Self: Clone,
+            crate::Servers: axum_mongodb::HasModel<T, Server = Self>,
         {
-            type Rejection = std::convert::Infallible;
+            type Rejection = axum_mongodb::MongoDbRejection;
             async fn from_request_parts(
-                parts: &mut axum::http::request::Parts,
-                _state: &S,
-            ) -> Result<Self, Self::Rejection> {
-                let dbs = parts
-                    .extensions
-                    .get::<axum_mongodb::MongoDbServer<Self>>()
-                    .expect("can not get MongoDbServer");
-                Ok(dbs.servers.clone())
+                parts: &mut axum_mongodb::__private::axum::http::request::Parts,
+                state: &S,
+            ) -> ::std::result::Result<Self, Self::Rejection> {
+                let dbs = <axum_mongodb::MongoDbServer<crate::Servers> as axum_mongodb::__private::axum::extract::FromRequestParts<S>>::from_request_parts(parts, state).await?;
+                Ok(axum_mongodb::HasModel::<T>::server(&dbs.servers).clone())
+            }
+        }
+
+        #[axum_mongodb::__private::async_trait]
+        impl<S> axum_mongodb::__private::axum::extract::FromRequestParts<S> for crate::Servers
+        where
+            S: Send + Sync,
+        {
+            type Rejection = axum_mongodb::MongoDbRejection;
+            async fn from_request_parts(
+                parts: &mut axum_mongodb::__private::axum::http::request::Parts,
+                state: &S,
+            ) -> ::std::result::Result<Self, Self::Rejection> {
+                let dbs = <axum_mongodb::MongoDbServer<Self> as axum_mongodb::__private::axum::extract::FromRequestParts<S>>::from_request_parts(parts, state).await?;
+                Ok(dbs.servers)
+            }
+        }
+
+        // State中包含MongoDbServer<Servers>时，可以通过State<Server<T>>以及State<Servers>提取
+        impl<S, T> axum_mongodb::__private::axum::extract::FromRef<S> for Server<T>
+        where
+            Self: Clone,
+            axum_mongodb::MongoDbServer<crate::Servers>: axum_mongodb::__private::axum::extract::FromRef<S>,
+            crate::Servers: axum_mongodb::HasModel<T, Server = Self>,
+        {
+            fn from_ref(state: &S) -> Self {
+                let dbs = <axum_mongodb::MongoDbServer<crate::Servers> as axum_mongodb::__private::axum::extract::FromRef<S>>::from_ref(state);
+                axum_mongodb::HasModel::<T>::server(&dbs.servers).clone()
+            }
+        }
+
+        impl<S> axum_mongodb::__private::axum::extract::FromRef<S> for crate::Servers
+        where
+            axum_mongodb::MongoDbServer<Self>: axum_mongodb::__private::axum::extract::FromRef<S>,
+        {
+            fn from_ref(state: &S) -> Self {
+                <axum_mongodb::MongoDbServer<Self> as axum_mongodb::__private::axum::extract::FromRef<S>>::from_ref(state).servers
             }
         }
         #st
@@ -154,19 +467,56 @@ pub fn main(_attr: TokenStream, input: TokenStream) -> TokenStream {
     res.into()
 }
 
-/// 用于宏内部，将收集到的元信息注入到结构体中
+/// 用于宏内部，根据main上声明的模型生成Servers结构体
 #[proc_macro_attribute]
-pub fn inject_meta(_attr: TokenStream, input: TokenStream) -> TokenStream {
+pub fn inject_meta(attr: TokenStream, input: TokenStream) -> TokenStream {
+    let mut options = MainOptions::default();
+    let parser = syn::meta::parser(|meta| options.parse_meta(meta));
+    parse_macro_input!(attr with parser);
     let st = parse_macro_input!(input as syn::ItemStruct);
-    column::inject_meta(&st)
+    column::inject_meta(&st, &options)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/**
+在库crate中导出模型列表，生成一个同名的宏，在使用[`macro@main`]的crate中通过`include(...)`引入，
+模型路径相对于库crate的根模块，也可以通过`alias = Type`指定Servers中的字段名
+
+# Example
+```rust,ignore
+// domain crate的lib.rs
+pub mod user;
+pub mod order;
+
+axum_mongodb::export_models!(domain_models: user::User, order_list = order::Order);
+
+// 使用domain crate
+#[axum_mongodb::main(models(Todo), include(domain::domain_models))]
+async fn main() {
+    //...
+}
+```
+*/
+#[proc_macro]
+pub fn export_models(input: TokenStream) -> TokenStream {
+    let export = parse_macro_input!(input as ExportModels);
+    column::export_models(&export)
         .unwrap_or_else(|e| e.to_compile_error())
         .into()
 }
 
+#[doc(hidden)]
+#[proc_macro]
+pub fn include_models(input: TokenStream) -> TokenStream {
+    let include = parse_macro_input!(input as IncludeModels);
+    column::servers(&include.options).into()
+}
+
 /**
 用于axum handler，用于替换extract类型，简化操作
 # Example
-```rust
+```rust,ignore
 #[axum_mongodb::inject]
 async fn db_test(servers: DBServers) -> impl IntoResponse {
     let db_name = servers.db.name();
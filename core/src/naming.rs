@@ -0,0 +1,305 @@
+use quote::quote;
+use syn::{LitStr, Result};
+
+// 集合命名策略，在编译期根据结构体名生成集合名
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NamingStrategy {
+    // 结构体名小写后加s，兼容旧版本的默认行为
+    #[default]
+    LowercaseS,
+    SnakeCase,
+    CamelCase,
+    PluralSnakeCase,
+    AsIs,
+}
+
+impl NamingStrategy {
+    pub const ALL: [Self; 5] = [
+        Self::LowercaseS,
+        Self::SnakeCase,
+        Self::CamelCase,
+        Self::PluralSnakeCase,
+        Self::AsIs,
+    ];
+
+    pub fn parse(value: &LitStr) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|naming| naming.name() == value.value())
+            .ok_or_else(|| {
+                syn::Error::new_spanned(
+                    value,
+                    "unknown naming strategy, expected one of: lowercase_s, snake_case, camelCase, plural_snake_case, as_is",
+                )
+            })
+    }
+
+    // 在ALL中的位置，对应Column::COLLECTION_NAMES的下标
+    pub fn index(self) -> usize {
+        Self::ALL.iter().position(|naming| *naming == self).unwrap()
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::LowercaseS => "lowercase_s",
+            Self::SnakeCase => "snake_case",
+            Self::CamelCase => "camelCase",
+            Self::PluralSnakeCase => "plural_snake_case",
+            Self::AsIs => "as_is",
+        }
+    }
+
+    pub fn apply(&self, struct_name: &str) -> String {
+        match self {
+            Self::LowercaseS => {
+                let mut name = struct_name.to_lowercase();
+                name.push('s');
+                name
+            }
+            Self::SnakeCase => split_words(struct_name).join("_"),
+            Self::CamelCase => split_words(struct_name)
+                .iter()
+                .enumerate()
+                .map(|(i, word)| {
+                    if i == 0 {
+                        word.clone()
+                    } else {
+                        capitalize(word)
+                    }
+                })
+                .collect(),
+            Self::PluralSnakeCase => {
+                let mut words = split_words(struct_name);
+                if let Some(last) = words.last_mut() {
+                    *last = pluralize(last);
+                }
+                words.join("_")
+            }
+            Self::AsIs => struct_name.to_string(),
+        }
+    }
+
+    // 对应运行时的axum_mongodb::NamingStrategy
+    pub fn to_tokens(self) -> proc_macro2::TokenStream {
+        match self {
+            Self::LowercaseS => quote!(axum_mongodb::NamingStrategy::LowercaseS),
+            Self::SnakeCase => quote!(axum_mongodb::NamingStrategy::SnakeCase),
+            Self::CamelCase => quote!(axum_mongodb::NamingStrategy::CamelCase),
+            Self::PluralSnakeCase => quote!(axum_mongodb::NamingStrategy::PluralSnakeCase),
+            Self::AsIs => quote!(axum_mongodb::NamingStrategy::AsIs),
+        }
+    }
+}
+
+// 将结构体名拆分为小写单词，例如HTTPRequest拆分为http、request
+fn split_words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    for part in name.split('_').filter(|part| !part.is_empty()) {
+        let chars: Vec<char> = part.chars().collect();
+        let mut word = String::new();
+        for (i, c) in chars.iter().enumerate() {
+            if i > 0 && c.is_uppercase() {
+                let prev = chars[i - 1];
+                let next_is_lower = chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+                if !prev.is_uppercase() || next_is_lower {
+                    words.push(std::mem::take(&mut word));
+                }
+            }
+            word.extend(c.to_lowercase());
+        }
+        words.push(word);
+    }
+    words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+// 不可数名词，复数形式与单数相同
+const UNCOUNTABLE: &[&str] = &[
+    "advice",
+    "data",
+    "deer",
+    "equipment",
+    "feedback",
+    "fish",
+    "information",
+    "media",
+    "metadata",
+    "money",
+    "music",
+    "news",
+    "rice",
+    "series",
+    "sheep",
+    "species",
+    "staff",
+    "software",
+    "traffic",
+];
+
+// 不规则复数
+const IRREGULAR: &[(&str, &str)] = &[
+    ("alumnus", "alumni"),
+    ("analysis", "analyses"),
+    ("appendix", "appendices"),
+    ("axis", "axes"),
+    ("basis", "bases"),
+    ("cactus", "cacti"),
+    ("calf", "calves"),
+    ("child", "children"),
+    ("criterion", "criteria"),
+    ("crisis", "crises"),
+    ("datum", "data"),
+    ("echo", "echoes"),
+    ("elf", "elves"),
+    ("focus", "foci"),
+    ("foot", "feet"),
+    ("fungus", "fungi"),
+    ("goose", "geese"),
+    ("half", "halves"),
+    ("hero", "heroes"),
+    ("index", "indices"),
+    ("knife", "knives"),
+    ("leaf", "leaves"),
+    ("life", "lives"),
+    ("loaf", "loaves"),
+    ("man", "men"),
+    ("matrix", "matrices"),
+    ("medium", "media"),
+    ("mouse", "mice"),
+    ("nucleus", "nuclei"),
+    ("ox", "oxen"),
+    ("person", "people"),
+    ("phenomenon", "phenomena"),
+    ("potato", "potatoes"),
+    ("quiz", "quizzes"),
+    ("radius", "radii"),
+    ("self", "selves"),
+    ("shelf", "shelves"),
+    ("stimulus", "stimuli"),
+    ("syllabus", "syllabi"),
+    ("thesis", "theses"),
+    ("thief", "thieves"),
+    ("tomato", "tomatoes"),
+    ("tooth", "teeth"),
+    ("torpedo", "torpedoes"),
+    ("vertex", "vertices"),
+    ("veto", "vetoes"),
+    ("wife", "wives"),
+    ("wolf", "wolves"),
+    ("woman", "women"),
+];
+
+// 可以作为复合词结尾的不规则复数，例如salesman、grandchild
+const IRREGULAR_SUFFIX: &[(&str, &str)] = &[
+    ("child", "children"),
+    ("foot", "feet"),
+    ("man", "men"),
+    ("person", "people"),
+    ("tooth", "teeth"),
+    ("wife", "wives"),
+];
+
+// 以man结尾但按规则变化的单词
+const REGULAR_MAN: &[&str] = &[
+    "caiman", "german", "human", "ottoman", "roman", "shaman", "talisman",
+];
+
+// 英文单词复数化，只处理小写单词
+fn pluralize(word: &str) -> String {
+    if UNCOUNTABLE.contains(&word) {
+        return word.to_string();
+    }
+    if let Some((_, plural)) = IRREGULAR.iter().find(|(singular, _)| *singular == word) {
+        return plural.to_string();
+    }
+    if !REGULAR_MAN.contains(&word) {
+        for (singular, plural) in IRREGULAR_SUFFIX {
+            if let Some(stem) = word.strip_suffix(singular) {
+                return format!("{stem}{plural}");
+            }
+        }
+    }
+    if ["s", "x", "z", "ch", "sh"]
+        .iter()
+        .any(|suffix| word.ends_with(suffix))
+    {
+        return format!("{word}es");
+    }
+    if let Some(stem) = word.strip_suffix('y') {
+        if stem.chars().last().is_some_and(|c| !"aeiou".contains(c)) {
+            return format!("{stem}ies");
+        }
+    }
+    format!("{word}s")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_words_handles_acronyms_and_underscores() {
+        assert_eq!(split_words("Todo"), ["todo"]);
+        assert_eq!(split_words("UserProfile"), ["user", "profile"]);
+        assert_eq!(split_words("HTTPRequest"), ["http", "request"]);
+        assert_eq!(split_words("RequestHTTP"), ["request", "http"]);
+        assert_eq!(split_words("user_Profile"), ["user", "profile"]);
+        assert_eq!(split_words("Snake__Case_"), ["snake", "case"]);
+    }
+
+    #[test]
+    fn pluralize_regular_words() {
+        assert_eq!(pluralize("todo"), "todos");
+        assert_eq!(pluralize("box"), "boxes");
+        assert_eq!(pluralize("status"), "statuses");
+        assert_eq!(pluralize("match"), "matches");
+        assert_eq!(pluralize("wish"), "wishes");
+        assert_eq!(pluralize("company"), "companies");
+        assert_eq!(pluralize("day"), "days");
+    }
+
+    #[test]
+    fn pluralize_irregular_and_uncountable_words() {
+        assert_eq!(pluralize("person"), "people");
+        assert_eq!(pluralize("child"), "children");
+        assert_eq!(pluralize("index"), "indices");
+        assert_eq!(pluralize("analysis"), "analyses");
+        assert_eq!(pluralize("news"), "news");
+        assert_eq!(pluralize("metadata"), "metadata");
+    }
+
+    #[test]
+    fn pluralize_compound_words() {
+        assert_eq!(pluralize("salesman"), "salesmen");
+        assert_eq!(pluralize("salesperson"), "salespeople");
+        assert_eq!(pluralize("grandchild"), "grandchildren");
+        assert_eq!(pluralize("housewife"), "housewives");
+        assert_eq!(pluralize("human"), "humans");
+        assert_eq!(pluralize("german"), "germans");
+    }
+
+    #[test]
+    fn apply_naming_strategies() {
+        let name = "HTTPRequest";
+        assert_eq!(NamingStrategy::LowercaseS.apply(name), "httprequests");
+        assert_eq!(NamingStrategy::SnakeCase.apply(name), "http_request");
+        assert_eq!(NamingStrategy::CamelCase.apply(name), "httpRequest");
+        assert_eq!(NamingStrategy::PluralSnakeCase.apply(name), "http_requests");
+        assert_eq!(NamingStrategy::AsIs.apply(name), "HTTPRequest");
+        assert_eq!(
+            NamingStrategy::PluralSnakeCase.apply("Salesman"),
+            "salesmen"
+        );
+        assert_eq!(
+            NamingStrategy::PluralSnakeCase.apply("UserCategory"),
+            "user_categories"
+        );
+    }
+}
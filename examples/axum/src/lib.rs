@@ -12,7 +12,7 @@ use todos::Todo;
 use crate::todos::todos_router;
 
 // 在lib中使用，这样生成的结构体才能在整个项目中使用
-#[axum_mongodb::main]
+#[axum_mongodb::main(models(Todo))]
 pub async fn start() -> Result<()> {
     // 初始化日志
     tracing_subscriber::fmt()
@@ -27,7 +27,7 @@ pub async fn start() -> Result<()> {
     let db = client.database("todo");
 
     // 定义State
-    let mongodb_server = MongoDbServer::<Servers>::new(db).await;
+    let mongodb_server = MongoDbServer::<Servers>::new(db).await?;
 
     let app = Router::new()
         .route("/", get(hello_world))
@@ -1,18 +1,17 @@
 use axum::{extract::Path, response::IntoResponse, Json};
 use serde::Deserialize;
 
-use super::Todo;
+use super::{server::TodoPatch, Todo};
 use crate::Server;
 
 #[derive(Debug, Deserialize)]
 pub struct TodoQuery {
     pub description: String,
-    pub completed: Option<bool>,
 }
 
 pub async fn create_todo(
     todo: Server<Todo>,
-    Json(TodoQuery { description, .. }): Json<TodoQuery>,
+    Json(TodoQuery { description }): Json<TodoQuery>,
 ) -> impl IntoResponse {
     let res = todo.create_todo(description).await.unwrap();
     Json(res)
@@ -23,6 +22,11 @@ pub async fn get_todos(todo: Server<Todo>) -> impl IntoResponse {
     Json(res)
 }
 
+pub async fn get_todo_summaries(todo: Server<Todo>) -> impl IntoResponse {
+    let res = todo.get_todo_summaries().await.unwrap();
+    Json(res)
+}
+
 pub async fn get_todo(todo: Server<Todo>, Path(id): Path<String>) -> impl IntoResponse {
     tracing::info!("get_todo: {}", id);
     let res = todo.get_todo(id).await.unwrap();
@@ -37,11 +41,8 @@ pub async fn delete_todo(todo: Server<Todo>, Path(id): Path<String>) -> impl Int
 pub async fn update_todo(
     todo: Server<Todo>,
     Path(id): Path<String>,
-    Json(TodoQuery {
-        description,
-        completed,
-    }): Json<TodoQuery>,
+    Json(patch): Json<TodoPatch>,
 ) -> impl IntoResponse {
-    let res = todo.update_todo(id, description, completed).await.unwrap();
+    let res = todo.update_todo(id, patch).await.unwrap();
     Json(res)
 }
@@ -1,5 +1,5 @@
 mod controller;
-use controller::{create_todo, delete_todo, get_todo, get_todos, update_todo};
+use controller::{create_todo, delete_todo, get_todo, get_todo_summaries, get_todos, update_todo};
 mod server;
 use axum::{
     routing::{get, post},
@@ -10,6 +10,7 @@ pub use server::Todo;
 pub fn todos_router() -> Router {
     Router::new()
         .route("/todos", post(create_todo).get(get_todos))
+        .route("/todos/summaries", get(get_todo_summaries))
         .route(
             "/todos/:id",
             get(get_todo).put(update_todo).delete(delete_todo),
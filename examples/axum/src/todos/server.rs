@@ -3,12 +3,13 @@ use anyhow::Result;
 use axum_mongodb::futures::TryStreamExt;
 use axum_mongodb::preload::*;
 use mongodb::{
-    bson::{self, doc, oid::ObjectId},
+    bson::{self, oid::ObjectId},
     results::{DeleteResult, InsertOneResult, UpdateResult},
 };
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Column, Deserialize, Serialize, Clone)]
+#[column(patch)]
 pub struct Todo {
     #[serde(
         serialize_with = "bson::serde_helpers::serialize_object_id_as_hex_string",
@@ -17,54 +18,57 @@ pub struct Todo {
     id: ObjectId,
     description: String,
     completed: bool,
+    #[column(immutable)]
     create_time: chrono::DateTime<chrono::Local>,
     update_time: chrono::DateTime<chrono::Local>,
 }
 
+#[derive(Debug, Deserialize, Serialize, Projection)]
+#[projection(of = Todo)]
+pub struct TodoSummary {
+    #[serde(
+        serialize_with = "bson::serde_helpers::serialize_object_id_as_hex_string",
+        rename = "_id"
+    )]
+    id: ObjectId,
+    description: String,
+}
+
 impl Server<Todo> {
     pub async fn create_todo(&self, description: String) -> Result<InsertOneResult> {
         Ok(self
-            .insert_one(
-                Todo {
-                    id: ObjectId::new(),
-                    description,
-                    completed: false,
-                    create_time: chrono::Local::now(),
-                    update_time: chrono::Local::now(),
-                },
-                None,
-            )
+            .insert(Todo {
+                id: ObjectId::new(),
+                description,
+                completed: false,
+                create_time: chrono::Local::now(),
+                update_time: chrono::Local::now(),
+            })
             .await?)
     }
 
     pub async fn get_todo(&self, id: String) -> Result<Option<Todo>> {
-        Ok(self.find_one(doc! {"_id":id}, None).await?)
+        let id = ObjectId::parse_str(id)?;
+        Ok(self.find_by_id(id).await?)
     }
 
     pub async fn get_todos(&self) -> Result<Vec<Todo>> {
-        let res = self.find(None, None).await?;
-        let todos = res.try_collect().await?;
-        Ok(todos)
+        Ok(self.find_many(None, None).await?)
+    }
+
+    pub async fn get_todo_summaries(&self) -> Result<Vec<TodoSummary>> {
+        let res = self.find_as::<TodoSummary>(None).await?;
+        Ok(res.try_collect().await?)
     }
 
     pub async fn delete_todo(&self, id: String) -> Result<DeleteResult> {
-        Ok(self.delete_one(doc! {"_id":id}, None).await?)
+        let id = ObjectId::parse_str(id)?;
+        Ok(self.delete_by_id(id).await?)
     }
 
-    pub async fn update_todo(
-        &self,
-        id: String,
-        description: String,
-        completed: Option<bool>,
-    ) -> Result<UpdateResult> {
-        let mut update = doc! {"$set": {"description": description, "update_time": chrono::Local::now().to_rfc3339()}};
-        if let Some(completed) = completed {
-            update
-                .get_document_mut("$set")
-                .unwrap()
-                .insert("completed", completed);
-        }
-        let res = self.update_one(doc! {"_id":id}, update, None).await?;
-        Ok(res)
+    pub async fn update_todo(&self, id: String, patch: TodoPatch) -> Result<UpdateResult> {
+        let id = ObjectId::parse_str(id)?;
+        let update = patch.into_update().set_update_time(chrono::Local::now());
+        Ok(self.update_by_id(id, update).await?)
     }
 }
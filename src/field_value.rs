@@ -0,0 +1,82 @@
+use mongodb::bson;
+use std::fmt;
+
+/**
+
+FieldValue
+可以作为字段`V`的值使用的类型，查询条件与更新操作中的值通过该trait转换为字段的类型，
+除了`V`本身外，`&str`可以作为`String`使用，`V`可以作为`Option<V>`使用
+
+与`Into<V>`不同，整数与浮点数字面量会直接推断为字段的类型，例如`u32`字段可以直接使用`1`
+
+*/
+pub trait FieldValue<V> {
+    fn into_value(self) -> V;
+}
+
+impl<V> FieldValue<V> for V {
+    fn into_value(self) -> V {
+        self
+    }
+}
+
+impl<V> FieldValue<Option<V>> for V {
+    fn into_value(self) -> Option<V> {
+        Some(self)
+    }
+}
+
+impl FieldValue<String> for &str {
+    fn into_value(self) -> String {
+        self.to_string()
+    }
+}
+
+impl FieldValue<Option<String>> for &str {
+    fn into_value(self) -> Option<String> {
+        Some(self.to_string())
+    }
+}
+
+/**
+
+ValueError
+查询条件或更新操作中的值无法按照字段的序列化方式转换为BSON，例如超出`i64`范围的`u64`，
+构建时不会panic，错误在[`crate::Filter::into_document`]与[`crate::Update::into_document`]时返回
+
+*/
+#[derive(Debug, Clone)]
+pub struct ValueError {
+    field: &'static str,
+    error: Box<bson::ser::Error>,
+}
+
+impl ValueError {
+    pub(crate) fn new(field: &'static str, error: bson::ser::Error) -> Self {
+        Self {
+            field,
+            error: Box::new(error),
+        }
+    }
+
+    /// 文档中的字段名
+    pub fn field(&self) -> &'static str {
+        self.field
+    }
+}
+
+impl fmt::Display for ValueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "failed to serialize value of field `{}`: {}",
+            self.field, self.error
+        )
+    }
+}
+
+impl std::error::Error for ValueError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(self.error.as_ref())
+    }
+}
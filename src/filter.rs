@@ -0,0 +1,327 @@
+use crate::{FieldValue, ValueError};
+use mongodb::bson::{self, doc, Bson, Document};
+use serde::ser;
+use std::{fmt, marker::PhantomData, ops};
+
+/**
+
+Filter
+模型`T`上的查询条件，由`#[derive(Column)]`生成的`T::filter()`构建，通过[`Filter::into_document`]或`try_into()`转换为[`Document`]，
+条件中的值无法序列化时返回[`ValueError`]，[`crate::Repository`]中的`find_many`、`exists`、`count`可以直接传入
+
+```rust,ignore
+let filter = Todo::filter()
+    .completed()
+    .eq(true)
+    .and(Todo::filter().create_time().gt(ts));
+let todos = todo.find(filter.into_document()?, None).await?;
+```
+
+*/
+pub struct Filter<T> {
+    document: Result<Document, ValueError>,
+    _model: PhantomData<fn() -> T>,
+}
+
+impl<T> Filter<T> {
+    fn new(document: Result<Document, ValueError>) -> Self {
+        Self {
+            document,
+            _model: PhantomData,
+        }
+    }
+
+    /// 同时满足两个条件，连续调用会合并到同一个`$and`中
+    pub fn and(self, other: Filter<T>) -> Self {
+        self.combine("$and", other)
+    }
+
+    /// 满足任意一个条件，连续调用会合并到同一个`$or`中
+    pub fn or(self, other: Filter<T>) -> Self {
+        self.combine("$or", other)
+    }
+
+    /// 转换为查询文档，条件中的值无法序列化时返回第一个错误
+    pub fn into_document(self) -> Result<Document, ValueError> {
+        self.document
+    }
+
+    fn combine(self, operator: &str, other: Filter<T>) -> Self {
+        let (mut document, other) = match (self.document, other.document) {
+            (Ok(document), Ok(other)) => (document, other),
+            (Err(e), _) | (_, Err(e)) => return Self::new(Err(e)),
+        };
+        if document.len() == 1 {
+            if let Ok(filters) = document.get_array_mut(operator) {
+                filters.push(other.into());
+                return Self::new(Ok(document));
+            }
+        }
+        Self::new(Ok(doc! { operator: [document, other] }))
+    }
+}
+
+/// 不满足该条件，对应`$nor`，例如`!Todo::filter().completed().eq(true)`
+impl<T> ops::Not for Filter<T> {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Self::new(self.document.map(|document| doc! { "$nor": [document] }))
+    }
+}
+
+impl<T> Clone for Filter<T> {
+    fn clone(&self) -> Self {
+        Self::new(self.document.clone())
+    }
+}
+
+impl<T> fmt::Debug for Filter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Filter").field(&self.document).finish()
+    }
+}
+
+impl<T> fmt::Display for Filter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.document {
+            Ok(document) => document.fmt(f),
+            Err(e) => e.fmt(f),
+        }
+    }
+}
+
+impl<T> TryFrom<Filter<T>> for Document {
+    type Error = ValueError;
+
+    fn try_from(filter: Filter<T>) -> Result<Self, Self::Error> {
+        filter.document
+    }
+}
+
+impl<T> TryFrom<Filter<T>> for Option<Document> {
+    type Error = ValueError;
+
+    fn try_from(filter: Filter<T>) -> Result<Self, Self::Error> {
+        filter.document.map(Some)
+    }
+}
+
+/**
+
+FilterField
+模型`T`中类型为`V`的字段，比较的值必须能通过[`FieldValue`]转换为`V`，并按照该字段的序列化方式（包括serde的`serialize_with`与`with`）转换为BSON，
+值无法序列化时返回[`ValueError`]，例如超出`i64`范围的`u64`
+
+*/
+pub struct FilterField<T, V> {
+    name: &'static str,
+    serialize: fn(&V) -> bson::ser::Result<Bson>,
+    _model: PhantomData<fn() -> T>,
+}
+
+impl<T, V> FilterField<T, V> {
+    #[doc(hidden)]
+    pub fn new(name: &'static str, serialize: fn(&V) -> bson::ser::Result<Bson>) -> Self {
+        Self {
+            name,
+            serialize,
+            _model: PhantomData,
+        }
+    }
+
+    /// 文档中的字段名
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn value(&self, value: V) -> Result<Bson, ValueError> {
+        (self.serialize)(&value).map_err(|e| ValueError::new(self.name, e))
+    }
+
+    fn operator(self, operator: &str, value: Result<Bson, ValueError>) -> Filter<T> {
+        Filter::new(value.map(|value| doc! { self.name: { operator: value } }))
+    }
+
+    /// 等于，对应`$eq`
+    pub fn eq(self, value: impl FieldValue<V>) -> Filter<T> {
+        let value = self.value(value.into_value());
+        self.operator("$eq", value)
+    }
+
+    /// 不等于，对应`$ne`
+    pub fn ne(self, value: impl FieldValue<V>) -> Filter<T> {
+        let value = self.value(value.into_value());
+        self.operator("$ne", value)
+    }
+
+    /// 大于，对应`$gt`
+    pub fn gt(self, value: impl FieldValue<V>) -> Filter<T> {
+        let value = self.value(value.into_value());
+        self.operator("$gt", value)
+    }
+
+    /// 大于等于，对应`$gte`
+    pub fn gte(self, value: impl FieldValue<V>) -> Filter<T> {
+        let value = self.value(value.into_value());
+        self.operator("$gte", value)
+    }
+
+    /// 小于，对应`$lt`
+    pub fn lt(self, value: impl FieldValue<V>) -> Filter<T> {
+        let value = self.value(value.into_value());
+        self.operator("$lt", value)
+    }
+
+    /// 小于等于，对应`$lte`
+    pub fn lte(self, value: impl FieldValue<V>) -> Filter<T> {
+        let value = self.value(value.into_value());
+        self.operator("$lte", value)
+    }
+
+    /// 等于其中任意一个值，对应`$in`
+    pub fn is_in<I>(self, values: I) -> Filter<T>
+    where
+        I: IntoIterator,
+        I::Item: FieldValue<V>,
+    {
+        let values = self.values(values);
+        self.operator("$in", values)
+    }
+
+    /// 不等于其中任何一个值，对应`$nin`
+    pub fn not_in<I>(self, values: I) -> Filter<T>
+    where
+        I: IntoIterator,
+        I::Item: FieldValue<V>,
+    {
+        let values = self.values(values);
+        self.operator("$nin", values)
+    }
+
+    /// 字段是否存在，对应`$exists`
+    pub fn exists(self, exists: bool) -> Filter<T> {
+        self.operator("$exists", Ok(Bson::Boolean(exists)))
+    }
+
+    fn values<I>(&self, values: I) -> Result<Bson, ValueError>
+    where
+        I: IntoIterator,
+        I::Item: FieldValue<V>,
+    {
+        values
+            .into_iter()
+            .map(|value| self.value(value.into_value()))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Bson::Array)
+    }
+}
+
+impl<T> FilterField<T, String> {
+    /// 正则匹配，对应`$regex`
+    pub fn regex(self, pattern: impl AsRef<str>) -> Filter<T> {
+        self.operator("$regex", Ok(Bson::String(pattern.as_ref().to_string())))
+    }
+}
+
+impl<T> FilterField<T, Option<String>> {
+    /// 正则匹配，对应`$regex`
+    pub fn regex(self, pattern: impl AsRef<str>) -> Filter<T> {
+        self.operator("$regex", Ok(Bson::String(pattern.as_ref().to_string())))
+    }
+}
+
+// 数组元素按照数组字段的序列化方式转换，例如`#[serde(with = "...")]`作用于整个Vec时同样作用于查询的元素
+impl<T, E> FilterField<T, Vec<E>> {
+    /// 数组中包含该元素
+    pub fn contains(self, value: impl FieldValue<E>) -> Filter<T> {
+        let value = self
+            .elements(vec![value.into_value()])
+            .map(|mut values| values.remove(0));
+        Filter::new(value.map(|value| doc! { self.name: value }))
+    }
+
+    /// 数组中包含全部元素，对应`$all`
+    pub fn contains_all<I>(self, values: I) -> Filter<T>
+    where
+        I: IntoIterator,
+        I::Item: FieldValue<E>,
+    {
+        let values = values.into_iter().map(FieldValue::into_value).collect();
+        let values = self.elements(values).map(Bson::Array);
+        self.operator("$all", values)
+    }
+
+    /// 数组长度，对应`$size`
+    pub fn size(self, size: u32) -> Filter<T> {
+        self.operator("$size", Ok(Bson::Int64(size.into())))
+    }
+
+    // 与数组字段一样序列化，结果必须是元素数量相同的数组
+    fn elements(&self, values: Vec<E>) -> Result<Vec<Bson>, ValueError> {
+        let len = values.len();
+        match self.value(values)? {
+            Bson::Array(values) if values.len() == len => Ok(values),
+            _ => Err(ValueError::new(
+                self.name,
+                ser::Error::custom("array field is not serialized as an array"),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Counter;
+
+    fn hits() -> FilterField<Counter, u64> {
+        FilterField::new("hits", bson::to_bson)
+    }
+
+    fn tags() -> FilterField<Counter, Vec<String>> {
+        FilterField::new("tags", |tags| {
+            bson::to_bson(
+                &tags
+                    .iter()
+                    .map(|tag| tag.to_uppercase())
+                    .collect::<Vec<_>>(),
+            )
+        })
+    }
+
+    #[test]
+    fn unserializable_value_is_an_error() {
+        let filter = hits().gt(u64::MAX);
+        assert_eq!(filter.clone().into_document().unwrap_err().field(), "hits");
+        let filter = !hits().eq(1).and(filter).or(hits().lt(5));
+        assert_eq!(filter.into_document().unwrap_err().field(), "hits");
+        assert!(Document::try_from(hits().is_in([1, u64::MAX])).is_err());
+    }
+
+    #[test]
+    fn combine_filters() {
+        let filter = hits().gt(1).and(hits().lt(5)).and(hits().ne(3));
+        assert_eq!(
+            filter.into_document().unwrap(),
+            doc! { "$and": [
+                { "hits": { "$gt": 1_i64 } },
+                { "hits": { "$lt": 5_i64 } },
+                { "hits": { "$ne": 3_i64 } },
+            ] }
+        );
+    }
+
+    #[test]
+    fn array_elements_use_field_serializer() {
+        assert_eq!(
+            tags().contains("rust").into_document().unwrap(),
+            doc! { "tags": "RUST" }
+        );
+        assert_eq!(
+            tags().contains_all(["a", "b"]).into_document().unwrap(),
+            doc! { "tags": { "$all": ["A", "B"] } }
+        );
+    }
+}
@@ -0,0 +1,644 @@
+use crate::{CollectionInit, CreateIndexOptions, InitError, InitOperation};
+use futures::TryStreamExt;
+use mongodb::{
+    bson::{self, Bson, Document},
+    error::ErrorKind,
+    options::Collation,
+    Collection, IndexModel,
+};
+use std::fmt;
+
+/**
+
+IndexSyncReport
+一次索引同步的结果，由[`crate::CollectionInit::init`]返回，列出的均为索引名
+
+*/
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IndexSyncReport {
+    /// 集合名
+    pub collection: String,
+    /// 与声明一致、未做任何修改的索引
+    pub kept: Vec<String>,
+    /// 新创建的索引
+    pub created: Vec<String>,
+    /// 选项发生变化、删除后重新创建的索引
+    pub recreated: Vec<String>,
+    /// 未声明而被删除的索引，只有使用`#[dropIndexes]`时才会删除
+    pub dropped: Vec<String>,
+}
+
+impl IndexSyncReport {
+    /// 是否对索引做了修改
+    pub fn is_changed(&self) -> bool {
+        !self.created.is_empty() || !self.recreated.is_empty() || !self.dropped.is_empty()
+    }
+}
+
+/**
+
+IndexAction
+对比声明的索引与已存在的索引后需要执行的操作
+
+*/
+#[derive(Debug, Clone)]
+pub enum IndexAction {
+    /// 与声明一致，保持不变
+    Keep { name: String },
+    /// 创建新的索引
+    Create {
+        name: String,
+        index: CreateIndexOptions,
+    },
+    /// 删除已存在的索引后重新创建，name为已存在的索引名
+    Recreate {
+        name: String,
+        index: CreateIndexOptions,
+    },
+    /// 删除未声明的索引
+    Drop { name: String },
+}
+
+impl IndexAction {
+    /// 索引名
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Keep { name }
+            | Self::Create { name, .. }
+            | Self::Recreate { name, .. }
+            | Self::Drop { name } => name,
+        }
+    }
+
+    /// 是否会删除已存在的索引
+    pub fn is_drop(&self) -> bool {
+        matches!(self, Self::Recreate { .. } | Self::Drop { .. })
+    }
+}
+
+impl fmt::Display for IndexAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Keep { name } => write!(f, "keep {name}"),
+            Self::Create { name, index } => write!(f, "create {name} {}", index.keys),
+            Self::Recreate { name, index } => write!(f, "recreate {name} {}", index.keys),
+            Self::Drop { name } => write!(f, "drop {name}"),
+        }
+    }
+}
+
+/**
+
+IndexPlan
+一个集合的索引同步计划，由[`plan_indexes`]以及[`crate::MongoDbServer::plan_indexes`]返回，不会修改数据库
+
+*/
+#[derive(Debug, Clone, Default)]
+pub struct IndexPlan {
+    /// 集合名
+    pub collection: String,
+    /// 按照执行顺序排列的操作
+    pub actions: Vec<IndexAction>,
+}
+
+impl IndexPlan {
+    /// 是否会修改索引
+    pub fn is_changed(&self) -> bool {
+        self.actions
+            .iter()
+            .any(|action| !matches!(action, IndexAction::Keep { .. }))
+    }
+
+    /// 会被删除的已存在的索引，包括需要重新创建的索引
+    pub fn drops(&self) -> impl Iterator<Item = &IndexAction> {
+        self.actions.iter().filter(|action| action.is_drop())
+    }
+}
+
+impl fmt::Display for IndexPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:", self.collection)?;
+        for action in &self.actions {
+            write!(f, "\n  {action}")?;
+        }
+        Ok(())
+    }
+}
+
+/// 索引同步模式，通过`#[axum_mongodb::main(sync_mode = "...")]`指定
+#[doc(hidden)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncMode {
+    /// `apply`：同步索引，默认值
+    Apply,
+    /// `plan`：只输出同步计划，不修改索引
+    Plan,
+    /// `off`：不做任何处理
+    Off,
+}
+
+/// 按照同步模式初始化集合的索引
+#[doc(hidden)]
+pub async fn init_collection<C>(server: &C, mode: SyncMode) -> Result<(), InitError>
+where
+    C: CollectionInit + Sync,
+{
+    match mode {
+        SyncMode::Apply => {
+            server.init().await?;
+        }
+        SyncMode::Plan => {
+            let _plan = server.plan().await?;
+            #[cfg(feature = "tracing")]
+            tracing::info!("index plan for {}", _plan);
+        }
+        SyncMode::Off => {}
+    }
+    Ok(())
+}
+
+/// 计算集合上的索引与声明的索引同步时需要执行的操作，不会修改数据库
+pub async fn plan_indexes<T>(
+    collection: &Collection<T>,
+    declared: &[CreateIndexOptions],
+    drop_undeclared: bool,
+) -> Result<IndexPlan, InitError>
+where
+    T: Send + Sync,
+{
+    let existing = list_indexes(collection).await?;
+    Ok(IndexPlan {
+        collection: collection.name().to_string(),
+        actions: diff_indexes(declared, &existing, drop_undeclared),
+    })
+}
+
+/// 将集合上的索引与声明的索引同步
+///
+/// 与声明完全一致的索引保持不变，keys相同但选项不同的索引删除后重新创建，
+/// `drop_undeclared`为true时删除未声明的索引（`_id_`除外），遇到第一个错误时停止并返回
+pub async fn sync_indexes<T>(
+    collection: &Collection<T>,
+    declared: &[CreateIndexOptions],
+    drop_undeclared: bool,
+) -> Result<IndexSyncReport, InitError>
+where
+    T: Send + Sync,
+{
+    let mut report = IndexSyncReport {
+        collection: collection.name().to_string(),
+        ..Default::default()
+    };
+    let existing = list_indexes(collection).await?;
+    let error = |name: &str, operation| {
+        let name = name.to_string();
+        let collection = collection.name().to_string();
+        move |e| InitError::new(&collection, Some(&name), operation, e)
+    };
+    for action in diff_indexes(declared, &existing, drop_undeclared) {
+        match action {
+            IndexAction::Keep { name } => report.kept.push(name),
+            IndexAction::Create { name, index } => {
+                collection
+                    .create_index(index.index_model(), None)
+                    .await
+                    .map_err(error(&name, InitOperation::CreateIndex))?;
+                report.created.push(name);
+            }
+            IndexAction::Recreate { name, index } => {
+                collection
+                    .drop_index(name.as_str(), None)
+                    .await
+                    .map_err(error(&name, InitOperation::DropIndex))?;
+                collection
+                    .create_index(index.index_model(), None)
+                    .await
+                    .map_err(error(&index.index_name(), InitOperation::CreateIndex))?;
+                report.recreated.push(name);
+            }
+            IndexAction::Drop { name } => {
+                collection
+                    .drop_index(name.as_str(), None)
+                    .await
+                    .map_err(error(&name, InitOperation::DropIndex))?;
+                report.dropped.push(name);
+            }
+        }
+    }
+    #[cfg(feature = "tracing")]
+    tracing::info!("{:#?}", report);
+    Ok(report)
+}
+
+// 集合不存在时没有任何索引
+async fn list_indexes<T>(collection: &Collection<T>) -> Result<Vec<IndexModel>, InitError>
+where
+    T: Send + Sync,
+{
+    let res = match collection.list_indexes(None).await {
+        Ok(cursor) => cursor.try_collect().await,
+        Err(e) if matches!(*e.kind, ErrorKind::Command(ref err) if err.code == 26) => Ok(vec![]),
+        Err(e) => Err(e),
+    };
+    res.map_err(|e| InitError::new(collection.name(), None, InitOperation::ListIndexes, e))
+}
+
+// 计算同步索引需要执行的操作，删除操作排在最前面
+// 集合的默认排序规则与`_id_`索引的排序规则一致，用于匹配未指定排序规则的索引
+fn diff_indexes(
+    declared: &[CreateIndexOptions],
+    existing: &[IndexModel],
+    drop_undeclared: bool,
+) -> Vec<IndexAction> {
+    let default_collation = existing
+        .iter()
+        .find(|index| existing_name(index) == "_id_")
+        .and_then(|index| index.options.as_ref())
+        .and_then(|options| options.collation.as_ref());
+    let mut matched = vec![false; existing.len()];
+    let mut actions = Vec::new();
+    for index in declared {
+        let same = (0..existing.len())
+            .find(|&i| !matched[i] && index.is_same_index(&existing[i], default_collation));
+        // 同名但不是同一个索引时，需要先删除旧索引才能创建
+        let conflict = || {
+            (0..existing.len())
+                .find(|&i| !matched[i] && existing_name(&existing[i]) == index.index_name())
+        };
+        match same.or_else(conflict) {
+            Some(i) => {
+                matched[i] = true;
+                let name = existing_name(&existing[i]);
+                if same == Some(i) && index.is_same_options(&existing[i]) {
+                    actions.push(IndexAction::Keep { name });
+                } else {
+                    actions.push(IndexAction::Recreate {
+                        name,
+                        index: index.clone(),
+                    });
+                }
+            }
+            None => actions.push(IndexAction::Create {
+                name: index.index_name(),
+                index: index.clone(),
+            }),
+        }
+    }
+    if !drop_undeclared {
+        return actions;
+    }
+    let drops = existing
+        .iter()
+        .zip(matched)
+        .filter(|(_, matched)| !matched)
+        .map(|(index, _)| existing_name(index))
+        .filter(|name| name != "_id_")
+        .map(|name| IndexAction::Drop { name });
+    drops.chain(actions).collect()
+}
+
+fn existing_name(index: &IndexModel) -> String {
+    index
+        .options
+        .as_ref()
+        .and_then(|options| options.name.clone())
+        .unwrap_or_default()
+}
+
+impl CreateIndexOptions {
+    /// 索引名，未指定时与MongoDB生成的默认名一致，例如`{ name: 1, age: -1 }`对应`name_1_age_-1`
+    pub fn index_name(&self) -> String {
+        if let Some(name) = &self.name {
+            return name.clone();
+        }
+        self.keys
+            .iter()
+            .map(|(key, value)| match value {
+                Bson::String(value) => format!("{key}_{value}"),
+                value => format!("{key}_{}", number(value).unwrap_or(1.0)),
+            })
+            .collect::<Vec<_>>()
+            .join("_")
+    }
+
+    // 除keys与排序规则外，其余选项是否与已存在的索引一致，未指定的选项按照数据库的默认值比较
+    fn is_same_options(&self, index: &IndexModel) -> bool {
+        let options = index.options.clone().unwrap_or_default();
+        let declared = self.index_model().options.unwrap_or_default();
+        if self.name.is_some() && declared.name != options.name {
+            return false;
+        }
+        if declared.unique.unwrap_or(false) != options.unique.unwrap_or(false)
+            || declared.sparse.unwrap_or(false) != options.sparse.unwrap_or(false)
+            || declared.expire_after != options.expire_after
+            || !same_option_document(
+                &declared.partial_filter_expression,
+                &options.partial_filter_expression,
+            )
+            || !same_option_document(&declared.wildcard_projection, &options.wildcard_projection)
+        {
+            return false;
+        }
+        if self.is_text() {
+            return declared.default_language.as_deref().unwrap_or("english")
+                == options.default_language.as_deref().unwrap_or("english")
+                && declared.language_override.as_deref().unwrap_or("language")
+                    == options.language_override.as_deref().unwrap_or("language")
+                && self.same_weights(options.weights.as_ref());
+        }
+        if self.is_2d() {
+            return declared.bits.unwrap_or(26) == options.bits.unwrap_or(26)
+                && declared.min.unwrap_or(-180.0) == options.min.unwrap_or(-180.0)
+                && declared.max.unwrap_or(180.0) == options.max.unwrap_or(180.0);
+        }
+        true
+    }
+
+    // 数据库中保存了每个文本字段的权重，未指定权重的字段为1
+    fn same_weights(&self, weights: Option<&Document>) -> bool {
+        let Some(weights) = weights else {
+            return false;
+        };
+        let text_keys = self
+            .keys
+            .iter()
+            .filter(|(_, value)| value.as_str() == Some("text"))
+            .map(|(key, _)| key)
+            .collect::<Vec<_>>();
+        text_keys.len() == weights.len()
+            && text_keys.iter().all(|key| {
+                let declared = self
+                    .weights
+                    .as_ref()
+                    .and_then(|weights| weights.get(key.as_str()))
+                    .and_then(number)
+                    .unwrap_or(1.0);
+                weights.get(key.as_str()).and_then(number) == Some(declared)
+            })
+    }
+
+    fn is_2d(&self) -> bool {
+        self.keys.values().any(|value| value.as_str() == Some("2d"))
+    }
+}
+
+fn number(value: &Bson) -> Option<f64> {
+    match value {
+        Bson::Int32(value) => Some(*value as f64),
+        Bson::Int64(value) => Some(*value as f64),
+        Bson::Double(value) => Some(*value),
+        _ => None,
+    }
+}
+
+// 数据库返回的数字类型可能与声明的不同，例如1与1.0，按照数值比较
+fn same_bson(a: &Bson, b: &Bson) -> bool {
+    match (a, b) {
+        (Bson::Document(a), Bson::Document(b)) => same_document(a, b),
+        (Bson::Array(a), Bson::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| same_bson(a, b))
+        }
+        _ => match (number(a), number(b)) {
+            (Some(a), Some(b)) => a == b,
+            _ => a == b,
+        },
+    }
+}
+
+pub(crate) fn same_document(a: &Document, b: &Document) -> bool {
+    a.len() == b.len()
+        && a.iter()
+            .zip(b)
+            .all(|((ka, va), (kb, vb))| ka == kb && same_bson(va, vb))
+}
+
+// 数据库返回的排序规则都包含完整的选项，按照序列化后的文档比较
+pub(crate) fn same_collation(a: &Collation, b: &Collation) -> bool {
+    match (bson::to_document(a), bson::to_document(b)) {
+        (Ok(a), Ok(b)) => same_document(&a, &b),
+        _ => false,
+    }
+}
+
+fn same_option_document(a: &Option<Document>, b: &Option<Document>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => same_document(a, b),
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IndexCollation;
+    use mongodb::{
+        bson::doc,
+        options::{CollationStrength, IndexOptions},
+    };
+
+    fn declared(keys: Document) -> CreateIndexOptions {
+        CreateIndexOptions {
+            keys,
+            ..Default::default()
+        }
+    }
+
+    fn existing(name: &str, keys: Document, mut options: IndexOptions) -> IndexModel {
+        options.name = Some(name.to_string());
+        IndexModel::builder().keys(keys).options(options).build()
+    }
+
+    fn id_index(collation: Option<Collation>) -> IndexModel {
+        let options = IndexOptions::builder().collation(collation).build();
+        existing("_id_", doc! { "_id": 1 }, options)
+    }
+
+    fn en_collation() -> Collation {
+        Collation::builder()
+            .locale("en")
+            .strength(CollationStrength::Secondary)
+            .build()
+    }
+
+    fn summary(actions: &[IndexAction]) -> Vec<String> {
+        actions.iter().map(|action| action.to_string()).collect()
+    }
+
+    #[test]
+    fn index_name_matches_mongodb_default() {
+        let index = declared(doc! { "name": 1, "age": -1, "location": "2dsphere" });
+        assert_eq!(index.index_name(), "name_1_age_-1_location_2dsphere");
+        let index = declared(doc! { "a": 1.0, "b": -1_i64 });
+        assert_eq!(index.index_name(), "a_1_b_-1");
+        let index = CreateIndexOptions {
+            name: Some("by_name".to_string()),
+            ..declared(doc! { "name": 1 })
+        };
+        assert_eq!(index.index_name(), "by_name");
+    }
+
+    #[test]
+    fn keep_same_index() {
+        let index = CreateIndexOptions {
+            unique: true,
+            ..declared(doc! { "name": 1 })
+        };
+        let existing = [
+            id_index(None),
+            existing(
+                "name_1",
+                doc! { "name": 1.0 },
+                IndexOptions::builder().unique(true).build(),
+            ),
+        ];
+        let actions = diff_indexes(&[index], &existing, true);
+        assert_eq!(summary(&actions), ["keep name_1"]);
+    }
+
+    #[test]
+    fn keep_index_with_numeric_options() {
+        let index = CreateIndexOptions {
+            partial_filter: Some(doc! { "age": { "$gt": 18 } }),
+            ..declared(doc! { "age": -1 })
+        };
+        let options = IndexOptions::builder()
+            .partial_filter_expression(doc! { "age": { "$gt": 18.0 } })
+            .build();
+        let existing = [existing("age_-1", doc! { "age": -1_i64 }, options)];
+        let actions = diff_indexes(&[index], &existing, false);
+        assert_eq!(summary(&actions), ["keep age_-1"]);
+    }
+
+    #[test]
+    fn create_missing_index() {
+        let actions = diff_indexes(&[declared(doc! { "name": 1 })], &[id_index(None)], true);
+        assert_eq!(summary(&actions), ["create name_1 { \"name\": 1 }"]);
+    }
+
+    #[test]
+    fn recreate_index_with_changed_options() {
+        let index = CreateIndexOptions {
+            unique: true,
+            ..declared(doc! { "name": 1 })
+        };
+        let existing = [existing(
+            "name_1",
+            doc! { "name": 1 },
+            IndexOptions::default(),
+        )];
+        let actions = diff_indexes(&[index], &existing, false);
+        assert_eq!(summary(&actions), ["recreate name_1 { \"name\": 1 }"]);
+        assert!(actions[0].is_drop());
+    }
+
+    #[test]
+    fn recreate_index_with_conflicting_name() {
+        let index = CreateIndexOptions {
+            name: Some("by_name".to_string()),
+            ..declared(doc! { "name": 1 })
+        };
+        let existing = [existing(
+            "by_name",
+            doc! { "email": 1 },
+            IndexOptions::default(),
+        )];
+        let actions = diff_indexes(&[index], &existing, false);
+        assert_eq!(summary(&actions), ["recreate by_name { \"name\": 1 }"]);
+    }
+
+    #[test]
+    fn recreate_index_with_changed_name() {
+        let index = CreateIndexOptions {
+            name: Some("by_name".to_string()),
+            ..declared(doc! { "name": 1 })
+        };
+        let existing = [existing(
+            "name_1",
+            doc! { "name": 1 },
+            IndexOptions::default(),
+        )];
+        let actions = diff_indexes(&[index], &existing, false);
+        assert_eq!(summary(&actions), ["recreate name_1 { \"name\": 1 }"]);
+    }
+
+    #[test]
+    fn drop_undeclared_indexes_except_id() {
+        let existing = [
+            id_index(None),
+            existing("old_1", doc! { "old": 1 }, IndexOptions::default()),
+            existing("name_1", doc! { "name": 1 }, IndexOptions::default()),
+        ];
+        let declared = [declared(doc! { "name": 1 })];
+        let actions = diff_indexes(&declared, &existing, true);
+        assert_eq!(summary(&actions), ["drop old_1", "keep name_1"]);
+        let actions = diff_indexes(&declared, &existing, false);
+        assert_eq!(summary(&actions), ["keep name_1"]);
+    }
+
+    #[test]
+    fn text_index_matches_fts_keys() {
+        let index = CreateIndexOptions {
+            weights: Some(doc! { "title": 10 }),
+            ..declared(doc! { "title": "text", "body": "text" })
+        };
+        let options = |weights| {
+            IndexOptions::builder()
+                .weights(weights)
+                .default_language("english".to_string())
+                .language_override("language".to_string())
+                .build()
+        };
+        let current = [existing(
+            "title_text_body_text",
+            doc! { "_fts": "text", "_ftsx": 1 },
+            options(doc! { "title": 10, "body": 1 }),
+        )];
+        let actions = diff_indexes(std::slice::from_ref(&index), &current, true);
+        assert_eq!(summary(&actions), ["keep title_text_body_text"]);
+
+        let changed = [existing(
+            "title_text_body_text",
+            doc! { "_fts": "text", "_ftsx": 1 },
+            options(doc! { "title": 1, "body": 1 }),
+        )];
+        let actions = diff_indexes(&[index], &changed, true);
+        assert_eq!(
+            summary(&actions),
+            ["recreate title_text_body_text { \"title\": \"text\", \"body\": \"text\" }"]
+        );
+    }
+
+    #[test]
+    fn index_without_collation_matches_collection_default() {
+        let options = IndexOptions::builder().collation(en_collation()).build();
+        let existing = [
+            id_index(Some(en_collation())),
+            existing("name_1", doc! { "name": 1 }, options),
+        ];
+        let actions = diff_indexes(&[declared(doc! { "name": 1 })], &existing, true);
+        assert_eq!(summary(&actions), ["keep name_1"]);
+    }
+
+    #[test]
+    fn index_collation_is_part_of_identity() {
+        let options = IndexOptions::builder().collation(en_collation()).build();
+        let existing = [
+            id_index(None),
+            existing("name_1", doc! { "name": 1 }, options),
+        ];
+        let actions = diff_indexes(&[declared(doc! { "name": 1 })], &existing, true);
+        assert_eq!(summary(&actions), ["recreate name_1 { \"name\": 1 }"]);
+
+        let index = CreateIndexOptions {
+            collation: Some(IndexCollation {
+                locale: "en".to_string(),
+                strength: Some(2),
+                ..Default::default()
+            }),
+            ..declared(doc! { "name": 1 })
+        };
+        let actions = diff_indexes(&[index], &existing, true);
+        assert_eq!(summary(&actions), ["keep name_1"]);
+    }
+}
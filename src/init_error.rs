@@ -0,0 +1,81 @@
+use std::fmt;
+
+/**
+
+InitError
+初始化集合索引失败时返回的错误，由[`crate::MongoDbServer`]的`new`以及[`crate::MongoDbServer::plan_indexes`]返回，
+包含集合名、索引名以及数据库返回的错误，调用方可以据此直接退出或者重试
+
+*/
+#[derive(Debug, Clone)]
+pub struct InitError {
+    /// 集合名
+    pub collection: String,
+    /// 索引名，获取已存在的索引失败时为None
+    pub index: Option<String>,
+    /// 失败的操作
+    pub operation: InitOperation,
+    /// 数据库返回的错误
+    pub source: mongodb::error::Error,
+}
+
+/// 初始化集合索引时执行的操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum InitOperation {
+    /// 获取已存在的索引
+    ListIndexes,
+    /// 创建索引
+    CreateIndex,
+    /// 删除索引
+    DropIndex,
+}
+
+impl InitError {
+    pub(crate) fn new(
+        collection: &str,
+        index: Option<&str>,
+        operation: InitOperation,
+        source: mongodb::error::Error,
+    ) -> Self {
+        Self {
+            collection: collection.to_string(),
+            index: index.map(str::to_string),
+            operation,
+            source,
+        }
+    }
+}
+
+impl fmt::Display for InitOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ListIndexes => write!(f, "list indexes"),
+            Self::CreateIndex => write!(f, "create index"),
+            Self::DropIndex => write!(f, "drop index"),
+        }
+    }
+}
+
+impl fmt::Display for InitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.index {
+            Some(index) => write!(
+                f,
+                "failed to {} `{}` on collection `{}`: {}",
+                self.operation, index, self.collection, self.source
+            ),
+            None => write!(
+                f,
+                "failed to {} on collection `{}`: {}",
+                self.operation, self.collection, self.source
+            ),
+        }
+    }
+}
+
+impl std::error::Error for InitError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
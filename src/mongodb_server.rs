@@ -1,7 +1,6 @@
-use crate::NewWithDb;
+use crate::{IndexPlan, InitError, MongoDbRejection, NewWithDb, PlanIndexes};
 use axum::{async_trait, extract::FromRequestParts};
 use mongodb::Database;
-use std::convert::Infallible;
 
 /**
 
@@ -10,6 +9,10 @@ MongoDbServer
 从而可以通过MongodbServer extract获取数据库和集合
 可以使用[`crate::inject`]以及[`crate::preload::DBServers`]简化extract的使用
 
+通过`.layer(Extension(mongodb_server))`注入后可以直接提取；也可以作为State的一部分，
+为其实现`FromRef<S>`（例如在State上使用`#[derive(FromRef)]`）后通过`State<MongoDbServer<Servers>>`、
+`State<Servers>`以及`State<Server<T>>`提取，State中不包含MongoDbServer时在编译期报错
+
 */
 #[derive(Debug, Clone)]
 pub struct MongoDbServer<T>
@@ -25,11 +28,28 @@ impl<T> NewWithDb for MongoDbServer<T>
 where
     T: Clone + NewWithDb,
 {
-    async fn new(db: Database) -> Self {
-        Self {
-            servers: T::new(db.clone()).await,
+    async fn new(db: Database) -> Result<Self, InitError> {
+        Ok(Self {
+            servers: T::new(db.clone()).await?,
             db,
-        }
+        })
+    }
+}
+
+impl<T> MongoDbServer<T>
+where
+    T: Clone + PlanIndexes,
+{
+    /// 计算所有模型的索引同步计划，不会修改数据库，可以在部署前检查将要创建、删除的索引
+    ///
+    /// ```rust,ignore
+    /// let plans = MongoDbServer::<Servers>::plan_indexes(db).await?;
+    /// for plan in &plans {
+    ///     println!("{plan}");
+    /// }
+    /// ```
+    pub async fn plan_indexes(db: Database) -> Result<Vec<IndexPlan>, InitError> {
+        T::plan_indexes(db).await
     }
 }
 
@@ -39,7 +59,7 @@ where
     S: Send + Sync,
     T: Clone + Send + Sync + 'static,
 {
-    type Rejection = Infallible;
+    type Rejection = MongoDbRejection;
     async fn from_request_parts(
         parts: &mut axum::http::request::Parts,
         _state: &S,
@@ -47,7 +67,7 @@ where
         let dbs = parts
             .extensions
             .get::<Self>()
-            .expect("can not get MongoDbServer");
+            .ok_or_else(MongoDbRejection::missing_extension::<Self>)?;
 
         Ok(dbs.clone())
     }
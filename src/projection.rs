@@ -0,0 +1,81 @@
+use mongodb::{
+    bson::Document,
+    error::Result,
+    options::{FindOneOptions, FindOptions},
+    Collection, Cursor,
+};
+use serde::de::DeserializeOwned;
+use std::marker::PhantomData;
+
+/**
+
+Projection
+模型`Model`的只读子集，由`#[derive(Projection)]`实现，通过`Server<Model>::find_as`查询时自动设置投影并反序列化为该类型
+
+*/
+pub trait Projection: DeserializeOwned + Unpin + Send + Sync {
+    /// 对应的模型
+    type Model;
+    /// 投影文档，例如`{ "_id": 1, "description": 1 }`
+    fn projection() -> Document;
+}
+
+/// 投影中的字段类型与模型中的字段类型是否兼容，`T`兼容`T`，`Option<T>`兼容`T`
+#[doc(hidden)]
+#[diagnostic::on_unimplemented(
+    message = "projection field type `{Self}` is not compatible with the model field type `{V}`",
+    label = "use `{V}` or `Option<{V}>`"
+)]
+pub trait ProjectFrom<V> {}
+
+impl<V> ProjectFrom<V> for V {}
+
+impl<V> ProjectFrom<V> for Option<V> {}
+
+/// 模型`T`中类型为`V`的字段，由`#[derive(Projection)]`生成的代码检查投影中的字段类型
+#[doc(hidden)]
+pub struct ProjectionField<T, V>(PhantomData<fn(&T) -> &V>);
+
+impl<T, V> ProjectionField<T, V> {
+    /// `field`只用于推导字段类型
+    pub fn new(_field: fn(&T) -> &V) -> Self {
+        Self(PhantomData)
+    }
+
+    /// 检查投影中的字段类型`P`与该字段兼容
+    pub fn check<P: ProjectFrom<V>>(self) {}
+}
+
+/// 使用`P`的投影查询，由`#[axum_mongodb::main]`生成的`Server<T>::find_as`调用
+#[doc(hidden)]
+pub async fn find_as<T, P>(
+    collection: &Collection<T>,
+    filter: impl Into<Option<Document>>,
+) -> Result<Cursor<P>>
+where
+    P: Projection<Model = T>,
+{
+    let options = FindOptions::builder().projection(P::projection()).build();
+    collection
+        .clone_with_type::<P>()
+        .find(filter, options)
+        .await
+}
+
+/// 使用`P`的投影查询一个文档，由`#[axum_mongodb::main]`生成的`Server<T>::find_one_as`调用
+#[doc(hidden)]
+pub async fn find_one_as<T, P>(
+    collection: &Collection<T>,
+    filter: impl Into<Option<Document>>,
+) -> Result<Option<P>>
+where
+    P: Projection<Model = T>,
+{
+    let options = FindOneOptions::builder()
+        .projection(P::projection())
+        .build();
+    collection
+        .clone_with_type::<P>()
+        .find_one(filter, options)
+        .await
+}
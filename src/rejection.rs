@@ -0,0 +1,55 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use std::fmt;
+
+/**
+
+MongoDbRejection
+从请求中提取[`crate::MongoDbServer`]、`Servers`以及`Server<T>`失败时返回的错误，
+响应状态码为500，开启`tracing`特性时同时通过tracing输出诊断信息
+
+*/
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum MongoDbRejection {
+    /// 请求扩展中没有对应的MongoDbServer，通常是忘记在路由上添加`.layer(Extension(mongodb_server))`
+    MissingExtension {
+        /// 缺失的扩展类型名
+        type_name: &'static str,
+    },
+}
+
+impl MongoDbRejection {
+    pub(crate) fn missing_extension<T>() -> Self {
+        Self::MissingExtension {
+            type_name: std::any::type_name::<T>(),
+        }
+    }
+}
+
+impl fmt::Display for MongoDbRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingExtension { type_name } => write!(
+                f,
+                "can not get {type_name} from request extensions, did you forget `.layer(Extension(mongodb_server))`?"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MongoDbRejection {}
+
+impl IntoResponse for MongoDbRejection {
+    fn into_response(self) -> Response {
+        #[cfg(feature = "tracing")]
+        tracing::error!("{}", self);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "MongoDbServer is not configured",
+        )
+            .into_response()
+    }
+}
@@ -0,0 +1,258 @@
+use crate::ValueError;
+use axum::async_trait;
+use futures::TryStreamExt;
+use mongodb::{
+    bson::{self, doc, Bson, Document},
+    options::{CountOptions, FindOptions, UpdateModifications},
+    results::{DeleteResult, InsertOneResult, UpdateResult},
+    Collection,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{convert::Infallible, fmt};
+
+/// 模型的`_id`，由`#[derive(Column)]`根据序列化为`_id`的字段实现，没有该字段时为`ObjectId`
+#[doc(hidden)]
+pub trait ModelId {
+    type Id: Send + Sync + 'static;
+    /// 按照`_id`字段的序列化方式转换为BSON
+    fn id_to_bson(id: &Self::Id) -> bson::ser::Result<Bson>;
+}
+
+/**
+
+RepositoryError
+[`Repository`]中所有方法返回的错误
+
+*/
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum RepositoryError {
+    /// 数据库返回的错误
+    Database(mongodb::error::Error),
+    /// `_id`无法序列化为BSON
+    Id(bson::ser::Error),
+    /// 查询条件或更新操作中的值无法序列化为BSON
+    Value(ValueError),
+    /// 更新文档为空，例如所有字段都为`None`的`{Model}Patch`，MongoDB不接受空的更新
+    EmptyUpdate,
+}
+
+impl fmt::Display for RepositoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Database(e) => write!(f, "database error: {e}"),
+            Self::Id(e) => write!(f, "failed to serialize _id: {e}"),
+            Self::Value(e) => e.fmt(f),
+            Self::EmptyUpdate => write!(f, "update document is empty"),
+        }
+    }
+}
+
+impl std::error::Error for RepositoryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Database(e) => Some(e),
+            Self::Id(e) => Some(e),
+            Self::Value(e) => Some(e),
+            Self::EmptyUpdate => None,
+        }
+    }
+}
+
+impl From<mongodb::error::Error> for RepositoryError {
+    fn from(error: mongodb::error::Error) -> Self {
+        Self::Database(error)
+    }
+}
+
+impl From<bson::ser::Error> for RepositoryError {
+    fn from(error: bson::ser::Error) -> Self {
+        Self::Id(error)
+    }
+}
+
+impl From<ValueError> for RepositoryError {
+    fn from(error: ValueError) -> Self {
+        Self::Value(error)
+    }
+}
+
+// 查询条件或更新操作为Document时转换不会失败
+impl From<Infallible> for RepositoryError {
+    fn from(error: Infallible) -> Self {
+        match error {}
+    }
+}
+
+/**
+
+Repository
+常用的增删改查，`#[axum_mongodb::main]`生成的`Server<T>`已经实现了该trait，`_id`的类型由模型中序列化为`_id`的字段决定
+
+```rust,ignore
+use axum_mongodb::preload::*;
+
+pub async fn get_todo(todo: Server<Todo>, Path(id): Path<String>) -> impl IntoResponse {
+    let id = ObjectId::parse_str(id).unwrap();
+    Json(todo.find_by_id(id).await.unwrap())
+}
+```
+
+*/
+#[async_trait]
+pub trait Repository<T>: Sync
+where
+    T: ModelId + Serialize + DeserializeOwned + Unpin + Send + Sync + 'static,
+{
+    /// 模型对应的集合
+    fn collection(&self) -> &Collection<T>;
+
+    /// 根据`_id`查询
+    async fn find_by_id(&self, id: T::Id) -> Result<Option<T>, RepositoryError> {
+        let filter = id_filter::<T>(&id)?;
+        Ok(self.collection().find_one(filter, None).await?)
+    }
+
+    /// 查询所有满足条件的文档，`filter`可以是`Document`、`None`或者`Todo::filter()`构建的条件
+    async fn find_many<F, O>(&self, filter: F, options: O) -> Result<Vec<T>, RepositoryError>
+    where
+        F: TryInto<Option<Document>> + Send,
+        F::Error: Into<RepositoryError>,
+        O: Into<Option<FindOptions>> + Send,
+    {
+        let filter = filter.try_into().map_err(Into::into)?;
+        let cursor = self.collection().find(filter, options).await?;
+        Ok(cursor.try_collect().await?)
+    }
+
+    /// 插入一个文档
+    async fn insert(&self, document: T) -> Result<InsertOneResult, RepositoryError> {
+        Ok(self.collection().insert_one(document, None).await?)
+    }
+
+    /// 根据`_id`替换整个文档
+    async fn replace_by_id(&self, id: T::Id, document: T) -> Result<UpdateResult, RepositoryError> {
+        let filter = id_filter::<T>(&id)?;
+        Ok(self
+            .collection()
+            .replace_one(filter, document, None)
+            .await?)
+    }
+
+    /// 根据`_id`更新，例如传入`Todo::update().set_completed(true)`，
+    /// 更新文档为空时不会访问数据库，返回[`RepositoryError::EmptyUpdate`]
+    async fn update_by_id<U>(&self, id: T::Id, update: U) -> Result<UpdateResult, RepositoryError>
+    where
+        U: TryInto<UpdateModifications> + Send,
+        U::Error: Into<RepositoryError>,
+    {
+        let filter = id_filter::<T>(&id)?;
+        let update = update_modifications(update)?;
+        Ok(self.collection().update_one(filter, update, None).await?)
+    }
+
+    /// 根据`_id`删除
+    async fn delete_by_id(&self, id: T::Id) -> Result<DeleteResult, RepositoryError> {
+        let filter = id_filter::<T>(&id)?;
+        Ok(self.collection().delete_one(filter, None).await?)
+    }
+
+    /// 是否存在满足条件的文档
+    async fn exists<F>(&self, filter: F) -> Result<bool, RepositoryError>
+    where
+        F: TryInto<Option<Document>> + Send,
+        F::Error: Into<RepositoryError>,
+    {
+        let filter = filter.try_into().map_err(Into::into)?;
+        let options = CountOptions::builder().limit(1).build();
+        Ok(self.collection().count_documents(filter, options).await? > 0)
+    }
+
+    /// 满足条件的文档数量
+    async fn count<F>(&self, filter: F) -> Result<u64, RepositoryError>
+    where
+        F: TryInto<Option<Document>> + Send,
+        F::Error: Into<RepositoryError>,
+    {
+        let filter = filter.try_into().map_err(Into::into)?;
+        Ok(self.collection().count_documents(filter, None).await?)
+    }
+}
+
+fn id_filter<T: ModelId>(id: &T::Id) -> Result<Document, RepositoryError> {
+    Ok(doc! { "_id": T::id_to_bson(id)? })
+}
+
+fn update_modifications<U>(update: U) -> Result<UpdateModifications, RepositoryError>
+where
+    U: TryInto<UpdateModifications>,
+    U::Error: Into<RepositoryError>,
+{
+    match update.try_into().map_err(Into::into)? {
+        UpdateModifications::Document(document) if document.is_empty() => {
+            Err(RepositoryError::EmptyUpdate)
+        }
+        update => Ok(update),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Update;
+    use mongodb::bson::oid::ObjectId;
+
+    struct Todo;
+
+    impl ModelId for Todo {
+        type Id = ObjectId;
+
+        fn id_to_bson(id: &Self::Id) -> bson::ser::Result<Bson> {
+            bson::to_bson(id)
+        }
+    }
+
+    struct Tag;
+
+    impl ModelId for Tag {
+        type Id = String;
+
+        fn id_to_bson(id: &Self::Id) -> bson::ser::Result<Bson> {
+            bson::to_bson(id)
+        }
+    }
+
+    struct Counter;
+
+    impl ModelId for Counter {
+        type Id = u64;
+
+        fn id_to_bson(id: &Self::Id) -> bson::ser::Result<Bson> {
+            bson::to_bson(id)
+        }
+    }
+
+    #[test]
+    fn id_filter_uses_id_serializer() {
+        let id = ObjectId::new();
+        assert_eq!(id_filter::<Todo>(&id).unwrap(), doc! { "_id": id });
+        assert_eq!(
+            id_filter::<Tag>(&"rust".to_string()).unwrap(),
+            doc! { "_id": "rust" }
+        );
+        assert!(matches!(
+            id_filter::<Counter>(&u64::MAX),
+            Err(RepositoryError::Id(_))
+        ));
+    }
+
+    #[test]
+    fn empty_update_is_rejected() {
+        let result = update_modifications(Update::<Todo>::new());
+        assert!(matches!(result, Err(RepositoryError::EmptyUpdate)));
+        let result = update_modifications(Document::new());
+        assert!(matches!(result, Err(RepositoryError::EmptyUpdate)));
+        let result = update_modifications(doc! { "$set": { "completed": true } });
+        assert!(matches!(result, Ok(UpdateModifications::Document(_))));
+    }
+}
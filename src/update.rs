@@ -0,0 +1,342 @@
+use crate::ValueError;
+use mongodb::{
+    bson::{self, doc, Bson, Document},
+    options::UpdateModifications,
+};
+use serde::ser;
+use std::{fmt, marker::PhantomData};
+
+/**
+
+Update
+模型`T`上的更新操作，由`#[derive(Column)]`生成的`T::update()`构建，每个字段对应`set_`、`unset_`、`inc_`、`push_`方法，
+值按照字段的序列化方式转换为BSON，无法序列化时[`Update::into_document`]或`try_into()`返回[`ValueError`]，
+[`crate::Repository::update_by_id`]可以直接传入；同一个字段只保留最后一次操作，例如`set_views(1).inc_views(1)`只包含`$inc`，
+连续的`inc_`累加增量，连续的`push_`合并为`{"$push": {field: {"$each": [..]}}}`
+
+```rust,ignore
+let update = Todo::update()
+    .set_description(description)
+    .set_completed(true)
+    .inc_views(1)
+    .push_tags("rust");
+let filter = Todo::filter().id().eq(id).into_document()?;
+self.update_one(filter, update.into_document()?, None).await?;
+```
+
+*/
+pub struct Update<T> {
+    document: Document,
+    // 第一个无法序列化的值，into_document时返回
+    error: Option<ValueError>,
+    _model: PhantomData<fn() -> T>,
+}
+
+// 同一个字段只能出现在一个更新操作符中，否则MongoDB会拒绝整个更新
+const OPERATORS: [&str; 4] = ["$set", "$unset", "$inc", "$push"];
+
+impl<T> Update<T> {
+    #[doc(hidden)]
+    pub fn new() -> Self {
+        Self {
+            document: Document::new(),
+            error: None,
+            _model: PhantomData,
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn set<V>(
+        self,
+        name: &'static str,
+        value: V,
+        serialize: fn(&V) -> bson::ser::Result<Bson>,
+    ) -> Self {
+        self.operator("$set", name, serialize(&value))
+    }
+
+    #[doc(hidden)]
+    pub fn unset(self, name: &'static str) -> Self {
+        self.operator("$unset", name, Ok(Bson::String(String::new())))
+    }
+
+    #[doc(hidden)]
+    pub fn inc<V>(
+        self,
+        name: &'static str,
+        value: V,
+        serialize: fn(&V) -> bson::ser::Result<Bson>,
+    ) -> Self {
+        let value = serialize(&value).and_then(|value| match value {
+            Bson::Int32(_) | Bson::Int64(_) | Bson::Double(_) | Bson::Decimal128(_) => Ok(value),
+            _ => Err(ser::Error::custom(
+                "numeric field is not serialized as a number",
+            )),
+        });
+        self.operator("$inc", name, value)
+    }
+
+    // values是只包含追加元素的字段值，与数组字段一样序列化后放入$each
+    #[doc(hidden)]
+    pub fn push<V>(
+        self,
+        name: &'static str,
+        values: V,
+        serialize: fn(&V) -> bson::ser::Result<Bson>,
+    ) -> Self {
+        let values = serialize(&values).and_then(|values| match values {
+            Bson::Array(values) => Ok(Bson::Document(doc! { "$each": values })),
+            _ => Err(ser::Error::custom(
+                "array field is not serialized as an array",
+            )),
+        });
+        self.operator("$push", name, values)
+    }
+
+    /// 是否没有任何更新操作，MongoDB不接受空的更新文档
+    pub fn is_empty(&self) -> bool {
+        self.document.is_empty()
+    }
+
+    /// 转换为更新文档，值无法序列化时返回第一个错误
+    pub fn into_document(self) -> Result<Document, ValueError> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(self.document),
+        }
+    }
+
+    // 同一个字段多次设置时以最后一次为准，包括不同的操作符，例如先set_再inc_时只保留$inc，
+    // 连续的$inc累加增量，连续的$push合并到同一个$each中
+    fn operator(
+        mut self,
+        operator: &str,
+        name: &'static str,
+        value: bson::ser::Result<Bson>,
+    ) -> Self {
+        let mut previous = None;
+        for other in OPERATORS {
+            let removed = self.remove(other, name);
+            if other == operator {
+                previous = removed;
+            }
+        }
+        let value = value.and_then(|value| match previous {
+            Some(previous) => merge(operator, previous, value),
+            None => Ok(value),
+        });
+        let value = match value {
+            Ok(value) => value,
+            Err(e) => {
+                self.error.get_or_insert(ValueError::new(name, e));
+                return self;
+            }
+        };
+        match self.document.get_document_mut(operator) {
+            Ok(fields) => {
+                fields.insert(name, value);
+            }
+            Err(_) => {
+                self.document.insert(operator, doc! { name: value });
+            }
+        }
+        self
+    }
+
+    fn remove(&mut self, operator: &str, name: &str) -> Option<Bson> {
+        let fields = self.document.get_document_mut(operator).ok()?;
+        let value = fields.remove(name);
+        if fields.is_empty() {
+            self.document.remove(operator);
+        }
+        value
+    }
+}
+
+// 合并同一个字段上连续的两次操作
+fn merge(operator: &str, previous: Bson, value: Bson) -> bson::ser::Result<Bson> {
+    match (operator, previous, value) {
+        ("$inc", previous, value) => add(&previous, &value)
+            .ok_or_else(|| ser::Error::custom("increments of the field cannot be added")),
+        ("$push", Bson::Document(mut previous), Bson::Document(mut value)) => {
+            if let (Ok(each), Ok(values)) = (
+                previous.get_array_mut("$each"),
+                value.get_array_mut("$each"),
+            ) {
+                each.append(values);
+            }
+            Ok(Bson::Document(previous))
+        }
+        (_, _, value) => Ok(value),
+    }
+}
+
+// 两次$inc的增量相加，i32溢出时使用i64
+fn add(previous: &Bson, value: &Bson) -> Option<Bson> {
+    let integer = |value: &Bson| match value {
+        Bson::Int32(value) => Some(i64::from(*value)),
+        Bson::Int64(value) => Some(*value),
+        _ => None,
+    };
+    match (previous, value) {
+        (Bson::Int32(a), Bson::Int32(b)) => Some(
+            a.checked_add(*b)
+                .map_or(Bson::Int64(i64::from(*a) + i64::from(*b)), Bson::Int32),
+        ),
+        (Bson::Double(a), Bson::Double(b)) => Some(Bson::Double(a + b)),
+        _ => integer(previous)?
+            .checked_add(integer(value)?)
+            .map(Bson::Int64),
+    }
+}
+
+impl<T> Default for Update<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for Update<T> {
+    fn clone(&self) -> Self {
+        Self {
+            document: self.document.clone(),
+            error: self.error.clone(),
+            _model: PhantomData,
+        }
+    }
+}
+
+impl<T> fmt::Debug for Update<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.error {
+            Some(error) => f.debug_tuple("Update").field(error).finish(),
+            None => f.debug_tuple("Update").field(&self.document).finish(),
+        }
+    }
+}
+
+impl<T> fmt::Display for Update<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.error {
+            Some(error) => error.fmt(f),
+            None => self.document.fmt(f),
+        }
+    }
+}
+
+impl<T> TryFrom<Update<T>> for Document {
+    type Error = ValueError;
+
+    fn try_from(update: Update<T>) -> Result<Self, Self::Error> {
+        update.into_document()
+    }
+}
+
+impl<T> TryFrom<Update<T>> for UpdateModifications {
+    type Error = ValueError;
+
+    fn try_from(update: Update<T>) -> Result<Self, Self::Error> {
+        update.into_document().map(UpdateModifications::Document)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Counter;
+
+    fn counter() -> Update<Counter> {
+        Update::new()
+    }
+
+    #[test]
+    fn unserializable_value_is_an_error() {
+        let update = counter()
+            .set("hits", u64::MAX, bson::to_bson)
+            .set("name", "a", bson::to_bson);
+        assert_eq!(update.clone().into_document().unwrap_err().field(), "hits");
+        assert!(UpdateModifications::try_from(update).is_err());
+        let update = counter().inc("hits", u64::MAX, bson::to_bson);
+        assert_eq!(update.into_document().unwrap_err().field(), "hits");
+        let update = counter().push("tags", 1, bson::to_bson);
+        assert_eq!(update.into_document().unwrap_err().field(), "tags");
+    }
+
+    #[test]
+    fn last_operation_on_a_field_wins() {
+        let update = counter()
+            .set("views", 1, bson::to_bson)
+            .inc("views", 1, bson::to_bson);
+        assert_eq!(
+            update.into_document().unwrap(),
+            doc! { "$inc": { "views": 1 } }
+        );
+
+        let update = counter()
+            .inc("views", 1, bson::to_bson)
+            .push("tags", vec!["a"], bson::to_bson)
+            .set("views", 2, bson::to_bson)
+            .unset("tags");
+        assert_eq!(
+            update.into_document().unwrap(),
+            doc! { "$set": { "views": 2 }, "$unset": { "tags": "" } }
+        );
+    }
+
+    #[test]
+    fn repeated_inc_adds_amounts() {
+        let update = counter()
+            .inc("views", 1, bson::to_bson)
+            .inc("views", 2, bson::to_bson)
+            .inc("score", 0.5, bson::to_bson)
+            .inc("score", 0.25, bson::to_bson);
+        assert_eq!(
+            update.into_document().unwrap(),
+            doc! { "$inc": { "views": 3, "score": 0.75 } }
+        );
+
+        let update = counter()
+            .inc("views", i32::MAX, bson::to_bson)
+            .inc("views", 1, bson::to_bson);
+        assert_eq!(
+            update.into_document().unwrap(),
+            doc! { "$inc": { "views": i64::from(i32::MAX) + 1 } }
+        );
+
+        let update =
+            counter()
+                .inc("views", i64::MAX, bson::to_bson)
+                .inc("views", 1i64, bson::to_bson);
+        assert_eq!(update.into_document().unwrap_err().field(), "views");
+    }
+
+    #[test]
+    fn repeated_push_collects_values() {
+        let update = counter()
+            .push("tags", vec!["a"], bson::to_bson)
+            .push("tags", vec!["b"], bson::to_bson)
+            .inc("views", 1, bson::to_bson);
+        assert_eq!(
+            update.into_document().unwrap(),
+            doc! { "$push": { "tags": { "$each": ["a", "b"] } }, "$inc": { "views": 1 } }
+        );
+
+        // 按照字段的序列化方式转换每个元素
+        let upper = |tags: &Vec<&str>| {
+            bson::to_bson(
+                &tags
+                    .iter()
+                    .map(|tag| tag.to_uppercase())
+                    .collect::<Vec<_>>(),
+            )
+        };
+        let update = counter()
+            .push("tags", vec!["a"], upper)
+            .push("tags", vec!["b"], upper);
+        assert_eq!(
+            update.into_document().unwrap(),
+            doc! { "$push": { "tags": { "$each": ["A", "B"] } } }
+        );
+    }
+}
@@ -0,0 +1,65 @@
+//! 只依赖axum-mongodb、serde与mongodb的模型，检查宏生成的代码不依赖tracing、futures、bson等crate
+
+use axum_mongodb::preload::*;
+use mongodb::bson::{oid::ObjectId, DateTime, Document};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, Fields)]
+#[serde(rename_all = "camelCase")]
+pub struct Address {
+    pub city: String,
+    pub zip_code: String,
+}
+
+#[derive(Debug, Clone, Column, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[column(patch, collation(locale = "en"))]
+#[dropIndexes]
+#[index(keys(name = 1, "address.city" = 1), unique)]
+#[wildcardIndex(exclude(secret))]
+pub struct Todo {
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    #[textIndex(weight = 10)]
+    pub name: String,
+    #[compoundIndex(other_fields("address.zip_code"(desc)))]
+    pub description: Option<String>,
+    #[singleIndex(partial_filter = r#"{ "completed": false }"#)]
+    pub completed: bool,
+    pub views: u32,
+    pub tags: Vec<String>,
+    #[ttlIndex(expire_after = "7d")]
+    #[column(immutable)]
+    pub create_time: DateTime,
+    #[geoIndex(kind = "2dsphere")]
+    pub location: Document,
+    pub secret: String,
+    pub address: Address,
+}
+
+#[derive(Debug, Deserialize, Projection)]
+#[projection(of = Todo)]
+pub struct TodoSummary {
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    pub name: String,
+}
+
+#[axum_mongodb::main(models(Todo), sync_mode = "off")]
+pub async fn app() {}
+
+pub async fn summaries(todo: &Server<Todo>) -> Result<Vec<TodoSummary>, RepositoryError> {
+    let filter = Todo::filter().completed().eq(false).into_document()?;
+    let mut cursor = todo.find_as::<TodoSummary>(filter).await?;
+    let mut summaries = Vec::new();
+    while cursor.advance().await? {
+        summaries.push(cursor.deserialize_current()?);
+    }
+    Ok(summaries)
+}
+
+pub async fn complete(todo: &Server<Todo>, id: ObjectId) -> Result<bool, RepositoryError> {
+    let update = Todo::update().set_completed(true).inc_views(1);
+    let result = todo.update_by_id(id, update).await?;
+    Ok(result.modified_count > 0)
+}
@@ -0,0 +1,17 @@
+use axum::Router;
+use axum_mongodb::preload::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Column, Serialize, Deserialize)]
+#[column(collection = "app_users")]
+pub struct User {
+    pub name: String,
+}
+
+// 需要写成models(app_users = User)
+#[axum_mongodb::main(models(User))]
+async fn app() -> Router {
+    Router::new()
+}
+
+fn main() {}
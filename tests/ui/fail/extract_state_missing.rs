@@ -0,0 +1,26 @@
+use axum::{extract::State, routing::get, Router};
+use axum_mongodb::preload::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Column, Serialize, Deserialize)]
+pub struct Todo {
+    pub description: String,
+}
+
+#[derive(Clone)]
+struct AppState {
+    _name: String,
+}
+
+async fn state_server(State(_todo): State<Server<Todo>>) {}
+
+#[axum_mongodb::main(models(Todo))]
+async fn app() -> Router {
+    Router::new()
+        .route("/server", get(state_server))
+        .with_state(AppState {
+            _name: String::new(),
+        })
+}
+
+fn main() {}
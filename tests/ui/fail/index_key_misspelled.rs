@@ -0,0 +1,17 @@
+use axum_mongodb::preload::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, Fields)]
+#[serde(rename_all = "camelCase")]
+pub struct Address {
+    pub zip_code: String,
+}
+
+#[derive(Debug, Clone, Column, Serialize, Deserialize)]
+pub struct User {
+    #[compoundIndex(other_fields("address.zipp"))]
+    pub name: String,
+    pub address: Address,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use axum_mongodb::preload::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Column, Serialize, Deserialize)]
+pub struct User {
+    #[compoundIndex(other_fields("name.first"))]
+    pub age: u32,
+    pub name: String,
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+use axum_mongodb::mongodb::bson::DateTime;
+use axum_mongodb::preload::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Column, Serialize, Deserialize)]
+#[column(patch)]
+pub struct Todo {
+    #[serde(rename = "_id")]
+    pub id: String,
+    pub description: String,
+    #[column(immutable)]
+    pub create_time: DateTime,
+}
+
+fn main() {
+    let patch = TodoPatch::default();
+    let _ = patch.create_time;
+    let _ = patch.id;
+    let _ = Todo::update().set_create_time(DateTime::now());
+    let _ = Todo::update().set_id("1");
+}
@@ -0,0 +1,15 @@
+use axum_mongodb::preload::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Column, Serialize, Deserialize)]
+pub struct Todo {
+    pub description: String,
+}
+
+#[derive(Debug, Deserialize, Projection)]
+#[projection(of = Todo)]
+pub struct TodoSummary {
+    pub title: String,
+}
+
+fn main() {}
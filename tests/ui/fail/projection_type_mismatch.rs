@@ -0,0 +1,17 @@
+use axum_mongodb::preload::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Column, Serialize, Deserialize)]
+pub struct Todo {
+    pub description: String,
+    pub completed: bool,
+}
+
+#[derive(Debug, Deserialize, Projection)]
+#[projection(of = Todo)]
+pub struct TodoSummary {
+    pub description: String,
+    pub completed: u32,
+}
+
+fn main() {}
@@ -0,0 +1,38 @@
+use axum::{routing::get, Extension, Router};
+use axum_mongodb::preload::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Column, Serialize, Deserialize)]
+pub struct Todo {
+    pub description: String,
+}
+
+// State中不包含MongoDbServer，通过Extension注入
+#[derive(Clone)]
+struct AppState {
+    _name: String,
+}
+
+async fn server(_todo: Server<Todo>) {}
+
+async fn servers(_servers: Servers) {}
+
+async fn mongodb_server(_mongo: MongoDbServer<Servers>) {}
+
+#[axum_mongodb::main(models(Todo))]
+async fn app(mongo: MongoDbServer<Servers>) -> Router {
+    let stateless = Router::new()
+        .route("/server", get(server))
+        .route("/servers", get(servers))
+        .route("/mongodb_server", get(mongodb_server));
+    let with_state = Router::new()
+        .route("/server", get(server))
+        .route("/servers", get(servers))
+        .route("/mongodb_server", get(mongodb_server))
+        .with_state(AppState {
+            _name: String::new(),
+        });
+    stateless.merge(with_state).layer(Extension(mongo))
+}
+
+fn main() {}
@@ -0,0 +1,40 @@
+use axum::{
+    extract::{FromRef, State},
+    routing::get,
+    Router,
+};
+use axum_mongodb::preload::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Column, Serialize, Deserialize)]
+pub struct Todo {
+    pub description: String,
+}
+
+#[derive(Clone)]
+struct AppState {
+    mongo: MongoDbServer<Servers>,
+}
+
+impl FromRef<AppState> for MongoDbServer<Servers> {
+    fn from_ref(state: &AppState) -> Self {
+        state.mongo.clone()
+    }
+}
+
+async fn state_server(State(_todo): State<Server<Todo>>) {}
+
+async fn state_servers(State(_servers): State<Servers>) {}
+
+async fn state_mongodb_server(State(_mongo): State<MongoDbServer<Servers>>) {}
+
+#[axum_mongodb::main(models(Todo))]
+async fn app(mongo: MongoDbServer<Servers>) -> Router {
+    Router::new()
+        .route("/server", get(state_server))
+        .route("/servers", get(state_servers))
+        .route("/mongodb_server", get(state_mongodb_server))
+        .with_state(AppState { mongo })
+}
+
+fn main() {}
@@ -0,0 +1,28 @@
+use axum_mongodb::preload::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Column, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Todo {
+    #[serde(rename = "_id")]
+    pub id: String,
+    pub update_time: String,
+}
+
+// 字段名常量生成在TodoFields中，不会与模型自己的关联常量冲突
+impl Todo {
+    pub const ID: u32 = 1;
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Fields)]
+#[serde(rename_all = "camelCase")]
+pub struct Address {
+    pub zip_code: String,
+}
+
+fn main() {
+    assert_eq!(Todo::ID, 1);
+    assert_eq!(TodoFields::ID, "_id");
+    assert_eq!(TodoFields::UPDATE_TIME, "updateTime");
+    assert_eq!(AddressFields::ZIP_CODE, "zipCode");
+}
@@ -0,0 +1,116 @@
+use axum_mongodb::mongodb::bson::{doc, oid::ObjectId, DateTime, Document};
+use axum_mongodb::preload::*;
+use axum_mongodb::{Column as _, CreateIndexOptions, IndexCollation};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Fields)]
+#[serde(rename_all = "camelCase")]
+pub struct Address {
+    pub city: String,
+    pub zip_code: String,
+}
+
+#[derive(Debug, Clone, Column, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[wildcardIndex(exclude(secret, "address.zip_code"))]
+pub struct User {
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    #[textIndex(weight = 10, default_language = "english")]
+    pub title: String,
+    #[textIndex(weight = 2)]
+    pub description: String,
+    #[ttlIndex(expire_after = "1h30m")]
+    pub expire_at: DateTime,
+    #[singleIndex(order = "hashed")]
+    pub tenant_id: String,
+    #[geoIndex(other_fields("address.city"))]
+    pub location: Document,
+    #[singleIndex(unique, partial_filter = r#"{ "deleted": false }"#)]
+    pub user_name: String,
+    #[singleIndex(unique, collation(locale = "en", strength = 2))]
+    pub email: String,
+    #[wildcardIndex]
+    pub metadata: Document,
+    pub secret: String,
+    pub address: Address,
+    pub deleted: bool,
+}
+
+// 模型上的默认排序规则用于除文本索引以外的所有索引
+#[derive(Debug, Clone, Column, Serialize, Deserialize)]
+#[column(collation(locale = "fr", numeric_ordering = true))]
+pub struct Article {
+    #[singleIndex(unique)]
+    pub slug: String,
+    #[singleIndex(collation(locale = "en"))]
+    pub author: String,
+    #[textIndex(default_language = "french")]
+    pub body: String,
+}
+
+fn index(indexes: &[CreateIndexOptions], keys: Document) -> &CreateIndexOptions {
+    indexes
+        .iter()
+        .find(|index| index.keys == keys)
+        .unwrap_or_else(|| panic!("no index with keys {keys}"))
+}
+
+fn main() {
+    let indexes = User::indexes();
+    assert_eq!(indexes.len(), 8);
+
+    let ttl = index(&indexes, doc! { "expireAt": 1 });
+    assert_eq!(ttl.expire_after, Some(Duration::from_secs(5400)));
+
+    let wildcard = index(&indexes, doc! { "$**": 1 });
+    assert_eq!(
+        wildcard.wildcard_projection,
+        Some(doc! { "secret": 0, "address.zipCode": 0 })
+    );
+    let wildcard = index(&indexes, doc! { "metadata.$**": 1 });
+    assert_eq!(wildcard.wildcard_projection, None);
+
+    // 多个字段上的textIndex合并为一个索引
+    let text = index(&indexes, doc! { "title": "text", "description": "text" });
+    assert_eq!(text.weights, Some(doc! { "title": 10, "description": 2 }));
+    assert_eq!(text.default_language.as_deref(), Some("english"));
+
+    let hashed = index(&indexes, doc! { "tenantId": "hashed" });
+    assert!(!hashed.unique);
+
+    index(&indexes, doc! { "location": "2dsphere", "address.city": 1 });
+
+    let partial = index(&indexes, doc! { "userName": 1 });
+    assert!(partial.unique);
+    assert_eq!(partial.partial_filter, Some(doc! { "deleted": false }));
+
+    let collation = index(&indexes, doc! { "email": 1 });
+    assert_eq!(
+        collation.collation,
+        Some(IndexCollation {
+            locale: "en".to_string(),
+            strength: Some(2),
+            ..Default::default()
+        })
+    );
+
+    let indexes = Article::indexes();
+    assert_eq!(
+        index(&indexes, doc! { "slug": 1 }).collation,
+        Some(IndexCollation {
+            locale: "fr".to_string(),
+            numeric_ordering: Some(true),
+            ..Default::default()
+        })
+    );
+    assert_eq!(
+        index(&indexes, doc! { "author": 1 }).collation,
+        Some(IndexCollation {
+            locale: "en".to_string(),
+            ..Default::default()
+        })
+    );
+    assert_eq!(index(&indexes, doc! { "body": "text" }).collation, None);
+}
@@ -0,0 +1,76 @@
+use axum::Router;
+use axum_mongodb::{Column as _, NamingStrategy};
+
+pub mod todo {
+    use axum_mongodb::preload::*;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, Column, Serialize, Deserialize)]
+    pub struct Todo {
+        pub description: String,
+    }
+
+    #[derive(Debug, Clone, Column, Serialize, Deserialize)]
+    #[column(collection = "app_users")]
+    pub struct User {
+        pub name: String,
+    }
+}
+
+pub mod event {
+    use axum_mongodb::preload::*;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct OrderPlaced {
+        pub order_id: String,
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct OrderShipped {
+        pub tracking: String,
+    }
+
+    // 每个实例对应一个集合
+    #[derive(Debug, Clone, Column, Serialize, Deserialize)]
+    #[column(instances(OrderEvent = Event<OrderPlaced>, ShipmentEvent = Event<OrderShipped>))]
+    pub struct Event<T> {
+        pub payload: T,
+    }
+}
+
+// 模型路径相对于crate根目录
+axum_mongodb::export_models!(event_models: event::OrderEvent, shipments = event::ShipmentEvent);
+
+#[axum_mongodb::main(models(todo::Todo, app_users = todo::User), include(event_models))]
+async fn app() -> Router {
+    Router::new()
+}
+
+fn servers(
+    servers: &Servers,
+) -> (
+    &Server<todo::Todo>,
+    &Server<todo::User>,
+    &Server<event::OrderEvent>,
+    &Server<event::ShipmentEvent>,
+) {
+    (
+        &servers.todos,
+        &servers.app_users,
+        &servers.orderevents,
+        &servers.shipments,
+    )
+}
+
+fn main() {
+    let _ = servers;
+    let naming = NamingStrategy::LowercaseS;
+    assert_eq!(todo::Todo::collection_name(naming), "todos");
+    assert_eq!(todo::User::collection_name(naming), "app_users");
+    assert_eq!(event::OrderEvent::collection_name(naming), "orderevents");
+    assert_eq!(
+        event::ShipmentEvent::collection_name(naming),
+        "shipmentevents"
+    );
+}
@@ -0,0 +1,48 @@
+use axum_mongodb::preload::*;
+use serde::{Deserialize, Serialize};
+
+// 模型自己定义了filter与update，不生成构建器
+#[derive(Debug, Clone, Column, Serialize, Deserialize)]
+#[column(no_builders)]
+pub struct Todo {
+    #[serde(rename = "_id")]
+    pub id: String,
+    pub description: String,
+}
+
+impl Todo {
+    pub fn filter(&self) -> bool {
+        !self.description.is_empty()
+    }
+
+    pub fn update(&mut self, description: &str) {
+        self.description = description.to_string();
+    }
+}
+
+pub struct TodoFilter;
+
+pub struct TodoUpdate;
+
+// 投影的类型检查不依赖构建器
+#[derive(Debug, Deserialize, Projection)]
+#[projection(of = Todo)]
+pub struct TodoSummary {
+    pub description: String,
+}
+
+fn main() {
+    let mut todo = Todo {
+        id: "1".to_string(),
+        description: String::new(),
+    };
+    assert!(!todo.filter());
+    todo.update("rust");
+    assert!(todo.filter());
+    let _ = (TodoFilter, TodoUpdate);
+    assert_eq!(TodoFields::DESCRIPTION, "description");
+    assert_eq!(
+        <TodoSummary as axum_mongodb::Projection>::projection(),
+        axum_mongodb::__private::doc! { "_id": 0, "description": 1 }
+    );
+}
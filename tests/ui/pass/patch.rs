@@ -0,0 +1,56 @@
+use axum_mongodb::mongodb::bson::{doc, from_document, DateTime};
+use axum_mongodb::preload::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Column, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[column(patch)]
+pub struct Todo {
+    #[serde(rename = "_id")]
+    pub id: String,
+    pub description: String,
+    pub note: Option<String>,
+    #[column(immutable)]
+    pub create_time: DateTime,
+    pub update_time: DateTime,
+}
+
+fn main() {
+    // 不存在的字段不会生成$set，_id与immutable字段不在TodoPatch中
+    let patch: TodoPatch = from_document(doc! {
+        "_id": "2",
+        "description": "rust",
+        "createTime": DateTime::from_millis(0),
+    })
+    .unwrap();
+    assert_eq!(patch.description.as_deref(), Some("rust"));
+    assert!(patch.note.is_none());
+    assert_eq!(
+        patch.into_update().into_document().unwrap(),
+        doc! { "$set": { "description": "rust" } }
+    );
+
+    // Option字段为null时设置为null
+    let patch: TodoPatch = from_document(doc! { "note": null }).unwrap();
+    assert_eq!(patch.note, Some(None));
+    assert_eq!(
+        patch.into_update().into_document().unwrap(),
+        doc! { "$set": { "note": null } }
+    );
+
+    let patch: TodoPatch = from_document(doc! { "note": "later" }).unwrap();
+    assert_eq!(patch.note, Some(Some("later".to_string())));
+
+    // 没有任何字段时更新为空，可以继续追加其他更新
+    let patch = TodoPatch::default();
+    assert!(patch.clone().into_update().is_empty());
+    let now = DateTime::from_millis(1);
+    assert_eq!(
+        patch
+            .into_update()
+            .set_update_time(now)
+            .into_document()
+            .unwrap(),
+        doc! { "$set": { "updateTime": now } }
+    );
+}
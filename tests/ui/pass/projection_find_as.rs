@@ -0,0 +1,48 @@
+use axum::Router;
+use axum_mongodb::futures::TryStreamExt;
+use axum_mongodb::mongodb::{bson::oid::ObjectId, error::Result};
+use axum_mongodb::preload::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Column, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Todo {
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    pub description: String,
+    pub completed: bool,
+    pub update_time: String,
+}
+
+#[derive(Debug, Deserialize, Projection)]
+#[projection(of = Todo)]
+#[serde(rename_all = "camelCase")]
+pub struct TodoSummary {
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    pub description: String,
+    pub update_time: Option<String>,
+}
+
+async fn summaries(todo: Server<Todo>) -> Result<Vec<TodoSummary>> {
+    let filter = Todo::filter().completed().eq(false).into_document().unwrap();
+    todo.find_as::<TodoSummary>(filter).await?.try_collect().await
+}
+
+async fn summary(todo: Server<Todo>, id: ObjectId) -> Result<Option<TodoSummary>> {
+    let filter = Todo::filter().id().eq(id).into_document().unwrap();
+    todo.find_one_as::<TodoSummary>(filter).await
+}
+
+#[axum_mongodb::main(models(Todo))]
+async fn app() -> Router {
+    Router::new()
+}
+
+fn main() {
+    let _ = (summaries, summary);
+    assert_eq!(
+        <TodoSummary as axum_mongodb::Projection>::projection(),
+        axum_mongodb::__private::doc! { "_id": 1, "description": 1, "updateTime": 1 }
+    );
+}
@@ -0,0 +1,52 @@
+use axum::Router;
+use axum_mongodb::mongodb::bson::oid::ObjectId;
+use axum_mongodb::preload::*;
+use axum_mongodb::ModelId;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Column, Serialize, Deserialize)]
+pub struct Todo {
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Column, Serialize, Deserialize)]
+pub struct Tag {
+    #[serde(rename = "_id")]
+    pub name: String,
+}
+
+// 没有序列化为_id的字段时id为ObjectId
+#[derive(Debug, Clone, Column, Serialize, Deserialize)]
+pub struct Note {
+    pub content: String,
+}
+
+#[axum_mongodb::main(models(Todo, Tag, Note))]
+async fn app() -> Router {
+    Router::new()
+}
+
+fn assert_repository<T, R>()
+where
+    T: ModelId + Serialize + for<'de> Deserialize<'de> + Unpin + Send + Sync + 'static,
+    R: Repository<T>,
+{
+}
+
+fn assert_id<T: ModelId<Id = I>, I>() {}
+
+async fn find_tag(tag: Server<Tag>) -> Result<Option<Tag>, RepositoryError> {
+    tag.find_by_id("rust".to_string()).await
+}
+
+fn main() {
+    assert_repository::<Todo, Server<Todo>>();
+    assert_repository::<Tag, Server<Tag>>();
+    assert_repository::<Note, Server<Note>>();
+    assert_id::<Todo, ObjectId>();
+    assert_id::<Tag, String>();
+    assert_id::<Note, ObjectId>();
+    let _ = find_tag;
+}